@@ -0,0 +1,285 @@
+use crate::render::{Canvas, Color};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::ops::Range;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_FPS: u32 = 20;
+const MAX_FPS: u32 = 1000;
+
+/// How often the keyboard is checked while waiting for the next frame.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+const HELP: &str = "space: pause, n: step, +/-: speed, q: quit";
+
+/// Draws frames in the terminal with ANSI escapes, at a given rate which can be changed from the keyboard,
+/// along with pausing, stepping through the frames and quitting.
+///
+/// The terminal is restored when the animation is dropped.
+pub struct Animation {
+    fps: u32,
+    paused: bool,
+    keyboard: Option<Keyboard>,
+    /// The terminal size, in columns and lines.
+    size: (usize, usize),
+    /// The line below the last frame drawn.
+    status_line: usize,
+}
+
+#[derive(Copy, Clone)]
+enum Key {
+    Pause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Animation {
+    pub fn new(fps: u32) -> Animation {
+        let animation = Animation {
+            fps: fps.clamp(1, MAX_FPS),
+            paused: false,
+            keyboard: Keyboard::open(),
+            size: terminal_size().unwrap_or((80, 24)),
+            status_line: 1,
+        };
+
+        print!("\x1b[?25l");
+        animation
+    }
+
+    /// Draws a frame, then waits until the next one is due, or the user steps to it while paused.
+    /// The index of the frame restarts from 0 for each animation, which clears the screen.
+    /// Returns an [`io::ErrorKind::Interrupted`] error when the user quits.
+    pub fn show(&mut self, title: &str, index: usize, canvas: &Canvas) -> io::Result<()> {
+        let start = Instant::now();
+        let (columns, lines) = self.size;
+
+        let mut screen = String::new();
+
+        if index == 0 {
+            screen += "\x1b[2J";
+        }
+
+        screen += "\x1b[H";
+        screen += &to_ansi(canvas, columns, lines.saturating_sub(1).max(1) * 2);
+        self.status_line = canvas.height().div_ceil(2).min(lines.saturating_sub(1)) + 1;
+
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(screen.as_bytes())?;
+        drop(stdout);
+
+        let status = |animation: &Animation| {
+            let state = match animation.paused {
+                true => "paused".to_string(),
+                false => format!("{} fps", animation.fps),
+            };
+
+            format!("{title}, frame {}, {state} ({HELP})", index + 1)
+        };
+
+        self.print_status(&status(self))?;
+
+        loop {
+            let keys = self.keyboard.as_mut().map_or(vec![], |i| i.read());
+
+            for &key in keys.iter() {
+                match key {
+                    Key::Quit => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::Interrupted,
+                            "Animation stopped",
+                        ))
+                    }
+                    Key::Step => {
+                        self.paused = true;
+                        return Ok(());
+                    }
+                    Key::Pause => self.paused = !self.paused,
+                    Key::Faster => self.fps = (self.fps * 2).min(MAX_FPS),
+                    Key::Slower => self.fps = (self.fps / 2).max(1),
+                }
+            }
+
+            if !keys.is_empty() {
+                self.print_status(&status(self))?;
+            }
+
+            let interval = Duration::from_secs(1) / self.fps;
+
+            match start.elapsed() {
+                elapsed if self.paused || elapsed + POLL_INTERVAL < interval => {
+                    thread::sleep(POLL_INTERVAL)
+                }
+                elapsed => {
+                    thread::sleep(interval.saturating_sub(elapsed));
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn print_status(&self, status: &str) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        let status = status.chars().take(self.size.0).collect::<String>();
+        write!(stdout, "\x1b[{};1H{status}\x1b[K", self.status_line)?;
+        stdout.flush()
+    }
+}
+
+impl Drop for Animation {
+    fn drop(&mut self) {
+        println!("\x1b[0m\x1b[?25h\x1b[{};1H", self.status_line);
+        io::stdout().flush().ok();
+    }
+}
+
+/// The terminal in non-canonical mode, so that the keys can be read as soon as they are pressed,
+/// and without blocking when none is.
+struct Keyboard {
+    tty: File,
+    /// The settings to restore, as given by `stty -g`.
+    saved: String,
+}
+
+impl Keyboard {
+    fn open() -> Option<Keyboard> {
+        let tty = File::open("/dev/tty").ok()?;
+        let saved = stty(&["-g"])?.trim().to_string();
+
+        // Without isig, Ctrl+C is read as a key, so that the settings are restored when quitting with it
+        stty(&["-icanon", "-echo", "-isig", "min", "0", "time", "0"])?;
+
+        Some(Keyboard { tty, saved })
+    }
+
+    fn read(&mut self) -> Vec<Key> {
+        let mut buffer = [0; 32];
+        let count = self.tty.read(&mut buffer).unwrap_or(0);
+
+        buffer[..count]
+            .iter()
+            .filter_map(|c| match c {
+                b' ' | b'p' => Some(Key::Pause),
+                b'n' | b'.' => Some(Key::Step),
+                b'+' | b'=' => Some(Key::Faster),
+                b'-' => Some(Key::Slower),
+                b'q' | b'Q' | 3 => Some(Key::Quit),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Drop for Keyboard {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+/// Runs `stty` on the terminal, and returns its output when it succeeds.
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(File::open("/dev/tty").ok()?)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn terminal_size() -> Option<(usize, usize)> {
+    let size = stty(&["size"])?;
+    let (lines, columns) = size.trim().split_once(' ')?;
+    let size = (columns.parse().ok()?, lines.parse().ok()?);
+
+    // Pseudo-terminals may report no size at all
+    (size.0 > 0 && size.1 > 0).then_some(size)
+}
+
+/// Encodes the part of the canvas around its focus which fits in `columns` by `rows` cells,
+/// drawing two rows of cells on each line with half blocks.
+fn to_ansi(canvas: &Canvas, columns: usize, rows: usize) -> String {
+    let (focus_x, focus_y) = canvas.focus();
+    let xs = viewport(canvas.width(), columns, focus_x);
+    let ys = viewport(canvas.height(), rows, focus_y);
+
+    let mut ansi = String::new();
+
+    for y in ys.clone().step_by(2) {
+        let mut previous = None;
+
+        for x in xs.clone() {
+            let top = canvas.get(x, y);
+            let bottom = match y + 1 < ys.end {
+                true => canvas.get(x, y + 1),
+                false => Color::BLACK,
+            };
+
+            if previous != Some((top, bottom)) {
+                write!(
+                    ansi,
+                    "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m",
+                    top.r, top.g, top.b, bottom.r, bottom.g, bottom.b
+                )
+                .unwrap();
+
+                previous = Some((top, bottom));
+            }
+
+            ansi.push('▀');
+        }
+
+        ansi += "\x1b[0m\x1b[K\r\n";
+    }
+
+    ansi
+}
+
+/// Returns the range of `available` cells out of `size` which is centered on `focus`, as far as possible.
+fn viewport(size: usize, available: usize, focus: usize) -> Range<usize> {
+    if size <= available {
+        return 0..size;
+    }
+
+    let start = focus.saturating_sub(available / 2).min(size - available);
+    start..start + available
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn viewport_follows_focus() {
+        assert_eq!(viewport(10, 20, 5), 0..10);
+        assert_eq!(viewport(100, 20, 5), 0..20);
+        assert_eq!(viewport(100, 20, 50), 40..60);
+        assert_eq!(viewport(100, 20, 99), 80..100);
+    }
+
+    #[test]
+    fn ansi_half_blocks() {
+        let mut canvas = Canvas::new(2, 3, Color::BLACK);
+        canvas.set(0, 0, Color::WHITE);
+        canvas.set(1, 0, Color::WHITE);
+
+        let ansi = to_ansi(&canvas, 80, 40);
+        let lines = ansi.split("\r\n").collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].matches('▀').count(), 2);
+        assert_eq!(lines[0].matches("\x1b[38;2;255;255;255m").count(), 1);
+
+        let cropped = to_ansi(&canvas, 1, 2);
+        assert_eq!(cropped.matches('▀').count(), 1);
+    }
+}
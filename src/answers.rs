@@ -0,0 +1,150 @@
+use crate::common::Part;
+use crate::error::ParseError;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+pub const DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+
+/// Known answers, read from a TOML-like file:
+///
+/// ```toml
+/// [day1]
+/// part1 = 24000
+/// part2 = "45000"
+/// ```
+#[derive(Default)]
+pub struct Answers {
+    answers: HashMap<(u32, Part), String>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read answers file {}: {e}", path.display()))?;
+
+        Answers::parse(&text).map_err(|e| format!("Invalid answers file {}: {e}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, ParseError> {
+        let mut answers = Answers::default();
+        let mut day = None;
+
+        for (index, line) in text.lines().enumerate() {
+            let at_line = |e: ParseError| e.at_line(index + 1);
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            if let Some(section) = trimmed.strip_prefix('[') {
+                let name = section
+                    .strip_suffix(']')
+                    .ok_or_else(|| at_line(ParseError::new("Expected ']'")))?
+                    .trim();
+
+                let number = name.strip_prefix("day").ok_or_else(|| {
+                    at_line(ParseError::at(line, name, "Expected a [dayN] section"))
+                })?;
+
+                day = Some(parse_number(line, number).map_err(at_line)?);
+                continue;
+            }
+
+            let (key, value) = trimmed
+                .split_once('=')
+                .ok_or_else(|| at_line(ParseError::new("Expected part1 = ... or part2 = ...")))?;
+
+            let key = key.trim();
+            let part = match key {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => {
+                    return Err(at_line(ParseError::at(
+                        line,
+                        key,
+                        "Expected part1 or part2",
+                    )))
+                }
+            };
+
+            let day =
+                day.ok_or_else(|| at_line(ParseError::new("Answer outside of a [dayN] section")))?;
+            let value = parse_value(line, value.trim()).map_err(at_line)?;
+
+            answers.answers.insert((day, part), value);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|i| i.as_str())
+    }
+
+    /// Compares an answer to the known one, ignoring trailing whitespace.
+    pub fn check(&self, day: u32, part: Part, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected.trim_end() == answer.trim_end() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+fn parse_number(line: &str, token: &str) -> Result<u32, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(line, token, format!("Invalid day: '{token}'")))
+}
+
+/// Parses either a bare value or a double-quoted string, which may use `\n`, `\"` and `\\`.
+fn parse_value(line: &str, token: &str) -> Result<String, ParseError> {
+    let Some(quoted) = token.strip_prefix('"') else {
+        return match token {
+            "" => Err(ParseError::new("Missing value").at_column(line.chars().count() + 1)),
+            _ => Ok(token.to_string()),
+        };
+    };
+
+    let mut value = String::new();
+    let mut chars = quoted.char_indices();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' if quoted[index + 1..].trim().is_empty() => return Ok(value),
+            '"' => {
+                return Err(ParseError::at(
+                    line,
+                    &quoted[index + 1..],
+                    "Unexpected characters",
+                ));
+            }
+            '\\' => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, '"')) => value.push('"'),
+                Some((_, '\\')) => value.push('\\'),
+                _ => {
+                    return Err(ParseError::at(
+                        line,
+                        &quoted[index..],
+                        "Invalid escape sequence",
+                    ))
+                }
+            },
+            c => value.push(c),
+        }
+    }
+
+    Err(ParseError::new("Missing closing quote").at_column(line.chars().count() + 1))
+}
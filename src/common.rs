@@ -1,6 +1,29 @@
-use std::io::stdin;
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Part {
+    One,
+    Two,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Parts {
+    Both,
+    Only(Part),
+}
+
+impl Part {
+    pub fn number(&self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
 
-pub fn get_input_lines() -> Vec<String> {
-    println!("Enter input:");
-    stdin().lines().map(|i| i.unwrap()).collect::<Vec<_>>()
+impl Parts {
+    pub fn includes(&self, part: Part) -> bool {
+        match self {
+            Parts::Both => true,
+            Parts::Only(only) => *only == part,
+        }
+    }
 }
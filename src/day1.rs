@@ -1,23 +1,69 @@
-use crate::common::get_input_lines;
-
-#[allow(dead_code)]
-pub fn run() {
-    let mut list = get_input_lines()
-        .iter()
-        .map(|line| line.parse::<i32>())
-        .fold(vec![0], |mut list, item| {
-            if let Ok(value) = item {
-                *list.last_mut().unwrap() += value;
-            } else {
+use crate::error::{parse_token, ParseError};
+use crate::solution::Solution;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<i32>, ParseError> {
+        let mut list = vec![0];
+
+        for (index, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
                 list.push(0);
+            } else {
+                let value =
+                    parse_token::<i32>(line, line.trim()).map_err(|e| e.at_line(index + 1))?;
+                *list.last_mut().unwrap() += value;
             }
-            list
-        });
+        }
+
+        Ok(list)
+    }
+
+    fn part1(&self, list: &Vec<i32>) -> i32 {
+        *list.iter().max().unwrap()
+    }
+
+    fn part2(&self, list: &Vec<i32>) -> i32 {
+        let mut list = list.clone();
+        list.sort_unstable_by(|a, b| b.cmp(a));
+        list.iter().take(3).sum::<i32>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
 
-    let max = list.iter().max().unwrap();
-    println!("Max calories: {}", max);
+    #[test]
+    fn part1_example() {
+        let input = Day1.parse(EXAMPLE).unwrap();
+        assert_eq!(Day1.part1(&input), 24000);
+    }
 
-    list.sort_unstable_by(|a, b| b.cmp(a));
-    let first3 = list.iter().take(3).sum::<i32>();
-    println!("Calories of first 3 elves: {}", first3);
+    #[test]
+    fn part2_example() {
+        let input = Day1.parse(EXAMPLE).unwrap();
+        assert_eq!(Day1.part2(&input), 45000);
+    }
 }
@@ -1,27 +1,74 @@
-use crate::common::get_input_lines;
+use crate::common::Part;
+use crate::error::{parse_lines, parse_token, ParseError};
+use crate::params;
+use crate::render::{Canvas, Color, Frames};
+use crate::solution::Solution;
 use std::str::FromStr;
 
+const CRT_WIDTH: usize = 40;
+
 #[derive(Copy, Clone)]
-enum Instruction {
+pub enum Instruction {
     Noop,
     AddX(i32),
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let input = get_input_lines()
-        .into_iter()
-        .map(|i| i.parse().unwrap())
-        .collect::<Vec<Instruction>>();
+pub struct Day10 {
+    /// The cycles during which the signal strength is sampled in part 1.
+    pub first_sample: i32,
+    pub sample_interval: i32,
+    pub last_sample: i32,
+}
+
+impl Default for Day10 {
+    fn default() -> Self {
+        Day10 {
+            first_sample: 20,
+            sample_interval: 40,
+            last_sample: 220,
+        }
+    }
+}
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(&self, input: &str) -> Result<Vec<Instruction>, ParseError> {
+        let program = parse_lines(input)?;
+
+        if program.is_empty() {
+            return Err(ParseError::new("Empty program"));
+        }
+
+        Ok(program)
+    }
+
+    fn part1(&self, input: &Vec<Instruction>) -> i32 {
+        part1(input, |cycle| {
+            (self.first_sample..=self.last_sample).contains(&cycle)
+                && (cycle - self.first_sample).checked_rem(self.sample_interval) == Some(0)
+        })
+    }
+
+    fn part2(&self, input: &Vec<Instruction>) -> String {
+        part2(input)
+    }
 
-    let result = part1(&input);
-    println!("Result (part 1): {result}");
+    fn render(&self, input: &Vec<Instruction>, part: Part, frames: &mut Frames) -> bool {
+        if part == Part::One {
+            return false;
+        }
+
+        render(input, frames);
+        true
+    }
 
-    println!("Result (part 2):");
-    println!("{}", part2(&input));
+    params!(first_sample, sample_interval, last_sample);
 }
 
-fn part1(input: &[Instruction]) -> i32 {
+fn part1(input: &[Instruction], is_sampled: impl Fn(i32) -> bool) -> i32 {
     let mut x = 1;
     let mut cycle_counter = -1;
     let mut input_iter = input.iter();
@@ -34,7 +81,7 @@ fn part1(input: &[Instruction]) -> i32 {
     loop {
         cycle_counter += 1;
 
-        if matches!(cycle_counter, 20 | 60 | 100 | 140 | 180 | 220) {
+        if is_sampled(cycle_counter) {
             result += cycle_counter * x;
         }
 
@@ -60,6 +107,21 @@ fn part1(input: &[Instruction]) -> i32 {
 }
 
 fn part2(input: &[Instruction]) -> String {
+    let mut output = String::new();
+
+    for (i, lit) in scan_crt(input).into_iter().enumerate() {
+        output += if lit { "#" } else { "." };
+
+        if i % CRT_WIDTH == CRT_WIDTH - 1 {
+            output += "\n";
+        }
+    }
+
+    output
+}
+
+/// Returns whether each pixel drawn by the CRT is lit, one per cycle.
+fn scan_crt(input: &[Instruction]) -> Vec<bool> {
     let mut x = 1;
     let mut cycle_counter = 0;
     let mut input_iter = input.iter();
@@ -67,20 +129,16 @@ fn part2(input: &[Instruction]) -> String {
     let mut current_instruction = *input_iter.next().unwrap();
     let mut current_cycles_left = current_instruction.cycle_count();
 
-    let mut output = String::new();
+    let mut pixels = vec![];
 
     loop {
         cycle_counter += 1;
         current_cycles_left -= 1;
 
-        let col = cycle_counter % 40;
+        let col = (cycle_counter - 1) % CRT_WIDTH as i32 + 1;
         let sprite = x..=(x + 2);
 
-        output += if sprite.contains(&col) { "#" } else { "." };
-
-        if col == 0 {
-            output += "\n";
-        }
+        pixels.push(sprite.contains(&col));
 
         if current_cycles_left > 0 {
             continue;
@@ -99,7 +157,29 @@ fn part2(input: &[Instruction]) -> String {
         current_cycles_left = current_instruction.cycle_count()
     }
 
-    output
+    pixels
+}
+
+/// Draws the CRT screen after each cycle.
+fn render(input: &[Instruction], frames: &mut Frames) {
+    let pixels = scan_crt(input);
+    let rows = pixels.len().div_ceil(CRT_WIDTH);
+
+    let draw = |count: usize| {
+        let mut canvas = Canvas::new(CRT_WIDTH, rows, Color::BLACK);
+
+        for (i, _) in pixels[..count].iter().enumerate().filter(|(_, &lit)| lit) {
+            canvas.set(i % CRT_WIDTH, i / CRT_WIDTH, Color::GREEN);
+        }
+
+        canvas
+    };
+
+    for count in 1..=pixels.len() {
+        frames.push(|| draw(count));
+    }
+
+    frames.finish(|| draw(pixels.len()));
 }
 
 impl Instruction {
@@ -112,14 +192,188 @@ impl Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let tokens = s.split_whitespace().collect::<Vec<_>>();
         Ok(match tokens[..] {
             ["noop"] => Instruction::Noop,
-            ["addx", count] => Instruction::AddX(count.parse().unwrap()),
-            _ => panic!("Invalid instruction"),
+            ["addx", count] => Instruction::AddX(parse_token(s, count)?),
+            _ => return Err(ParseError::new("Invalid instruction")),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop";
+
+    const EXAMPLE_IMAGE: &str = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day10::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::default().part1(&input), 13140);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day10::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::default().part2(&input), EXAMPLE_IMAGE);
+    }
+}
@@ -1,23 +1,39 @@
-use crate::common::get_input_lines;
-use regex::Regex;
+use crate::day11b;
+use crate::error::{parse_token, ParseError};
+use crate::params;
+use crate::parse::scan_line;
+use crate::solution::Solution;
 use std::collections::VecDeque;
 use std::str::FromStr;
 
 #[derive(Copy, Clone)]
 struct Item(i32);
 
-enum Operand {
+#[derive(Clone)]
+pub(crate) enum Operand {
     Old,
     Number(i32),
 }
 
-enum Operator {
+#[derive(Clone)]
+pub(crate) enum Operator {
     Addition,
     Multiplication,
 }
 
-struct Operation(Operand, Operator, Operand);
+#[derive(Clone)]
+pub(crate) struct Operation(pub(crate) Operand, pub(crate) Operator, pub(crate) Operand);
 
+/// The description of a monkey in the input, shared by both parts.
+pub(crate) struct Notes {
+    pub(crate) items: Vec<i32>,
+    pub(crate) operation: Operation,
+    pub(crate) test: i32,
+    pub(crate) when_true: usize,
+    pub(crate) when_false: usize,
+}
+
+#[derive(Clone)]
 struct Monkey {
     items: VecDeque<Item>,
     operation: Operation,
@@ -27,21 +43,49 @@ struct Monkey {
     inspected: usize,
 }
 
-struct Puzzle {
+#[derive(Clone)]
+pub struct Puzzle {
     monkeys: Vec<Monkey>,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let input = get_input_lines();
+pub struct Day11 {
+    /// The number of rounds in part 1.
+    pub rounds: usize,
+    /// The number of rounds in part 2, where worry levels are not divided.
+    pub long_rounds: usize,
+}
 
-    let mut puzzle = Puzzle::parse(&input);
-    let result = part1(&mut puzzle);
-    println!("Result (part 1): {result}");
+impl Default for Day11 {
+    fn default() -> Self {
+        Day11 {
+            rounds: 20,
+            long_rounds: 10_000,
+        }
+    }
 }
 
-fn part1(puzzle: &mut Puzzle) -> usize {
-    for _ in 0..20 {
+impl Solution for Day11 {
+    type Input = (Puzzle, day11b::Puzzle);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok((Puzzle::parse(input)?, day11b::Puzzle::parse(input)?))
+    }
+
+    fn part1(&self, (puzzle, _): &Self::Input) -> usize {
+        part1(&mut puzzle.clone(), self.rounds)
+    }
+
+    fn part2(&self, (_, puzzle): &Self::Input) -> usize {
+        day11b::part2(&mut puzzle.clone(), self.long_rounds)
+    }
+
+    params!(rounds, long_rounds);
+}
+
+fn part1(puzzle: &mut Puzzle, rounds: usize) -> usize {
+    for _ in 0..rounds {
         puzzle.play_round()
     }
 
@@ -52,72 +96,53 @@ fn part1(puzzle: &mut Puzzle) -> usize {
         .collect::<Vec<_>>();
 
     scores.sort_unstable_by(|a, b| b.cmp(a));
-    scores.iter().take(2).fold(1, |s, i| s * i)
+    scores.iter().take(2).product()
 }
 
-impl Puzzle {
-    fn parse(input: &Vec<String>) -> Puzzle {
-        let monkey_re = Regex::new(r"^Monkey (\d+):").unwrap();
-        let items_re = Regex::new(r"^ {2}Starting items: ([\d, ]+)").unwrap();
-        let operation_re =
-            Regex::new(r"^ {2}Operation: new = (old|-?\d+) ([+*]) (old|-?\d+)").unwrap();
-        let test_re = Regex::new(r"^ {2}Test: divisible by (\d+)").unwrap();
-        let test_result_re = Regex::new(r"^ {4}If (true|false): throw to monkey (\d+)").unwrap();
-
-        let mut monkeys = vec![];
-
-        for line in input.iter() {
-            if line.is_empty() {
-                continue;
-            }
+/// Parses the notes about each monkey, checking that they only throw to known monkeys.
+pub(crate) fn parse_notes(input: &str) -> Result<Vec<Notes>, ParseError> {
+    let lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .collect::<Vec<_>>();
 
-            if let Some(cap) = monkey_re.captures(line) {
-                let index = cap[1].parse::<usize>().unwrap();
-                if index != monkeys.len() {
-                    panic!()
-                }
-
-                monkeys.push(Monkey {
-                    items: VecDeque::new(),
-                    operation: Operation(
-                        Operand::Number(0),
-                        Operator::Addition,
-                        Operand::Number(0),
-                    ),
-                    test: 0,
-                    when_true: 0,
-                    when_false: 0,
-                    inspected: 0,
-                });
-            } else if let Some(cap) = items_re.captures(line) {
-                let monkey = monkeys.last_mut().unwrap();
-                let items = &cap[1];
-                monkey
-                    .items
-                    .extend(items.split(", ").map(|i| Item(i.parse().unwrap())));
-            } else if let Some(cap) = operation_re.captures(line) {
-                let monkey = monkeys.last_mut().unwrap();
-                monkey.operation = Operation(
-                    cap[1].parse().unwrap(),
-                    cap[2].parse().unwrap(),
-                    cap[3].parse().unwrap(),
-                );
-            } else if let Some(cap) = test_re.captures(line) {
-                let monkey = monkeys.last_mut().unwrap();
-                monkey.test = cap[1].parse().unwrap();
-            } else if let Some(cap) = test_result_re.captures(line) {
-                let monkey = monkeys.last_mut().unwrap();
-                if cap[1].parse().unwrap() {
-                    monkey.when_true = cap[2].parse().unwrap();
-                } else {
-                    monkey.when_false = cap[2].parse().unwrap();
-                }
-            } else {
-                panic!("Invalid input: {}", line);
-            }
+    let notes = lines
+        .chunks(6)
+        .enumerate()
+        .map(|(number, lines)| Notes::parse(number, lines))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if notes.is_empty() {
+        return Err(ParseError::new("No monkeys found"));
+    }
+
+    for (index, monkey) in notes.iter().enumerate() {
+        if monkey.when_true >= notes.len() || monkey.when_false >= notes.len() {
+            return Err(ParseError::new(format!(
+                "Monkey {index} throws to an unknown monkey"
+            )));
         }
+    }
+
+    Ok(notes)
+}
 
-        Puzzle { monkeys }
+impl Puzzle {
+    pub(crate) fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let monkeys = parse_notes(input)?
+            .into_iter()
+            .map(|notes| Monkey {
+                items: notes.items.into_iter().map(Item).collect(),
+                operation: notes.operation,
+                test: notes.test,
+                when_true: notes.when_true,
+                when_false: notes.when_false,
+                inspected: 0,
+            })
+            .collect();
+
+        Ok(Puzzle { monkeys })
     }
 
     fn play_round(&mut self) {
@@ -131,19 +156,54 @@ impl Puzzle {
     }
 }
 
+impl Notes {
+    /// Parses the 6 lines describing a monkey, given along with their index.
+    fn parse(number: usize, lines: &[(usize, &str)]) -> Result<Notes, ParseError> {
+        let &[header, items, operation, test, when_true, when_false] = lines else {
+            return Err(ParseError::new("Incomplete monkey description").at_line(lines[0].0 + 1));
+        };
+
+        scan_line(header, "Monkey {}:", |mut fields| {
+            match fields.parse::<usize>()? {
+                i if i == number => Ok(()),
+                _ => Err(fields.error("Unexpected monkey number")),
+            }
+        })?;
+
+        let items = scan_line(items, "  Starting items: {}", |mut fields| fields.list(","))?;
+
+        let operation = scan_line(operation, "  Operation: new = {} {} {}", |mut fields| {
+            Ok(Operation(fields.parse()?, fields.parse()?, fields.parse()?))
+        })?;
+
+        let test = scan_line(test, "  Test: divisible by {}", |mut fields| {
+            match fields.parse()? {
+                0 => Err(fields.error("Cannot test divisibility by zero")),
+                test => Ok(test),
+            }
+        })?;
+
+        let target = |line, pattern| scan_line(line, pattern, |mut fields| fields.parse());
+
+        Ok(Notes {
+            items,
+            operation,
+            test,
+            when_true: target(when_true, "    If true: throw to monkey {}")?,
+            when_false: target(when_false, "    If false: throw to monkey {}")?,
+        })
+    }
+}
+
 impl Monkey {
     fn play(&mut self) -> Vec<(Item, usize)> {
         let mut result = vec![];
 
-        loop {
-            if let Some(item) = self.items.pop_front() {
-                let new_item = self.operation.apply(item).relieve();
-                let target_monkey = self.target_monkey(new_item);
-                result.push((new_item, target_monkey));
-                self.inspected += 1;
-            } else {
-                break;
-            }
+        while let Some(item) = self.items.pop_front() {
+            let new_item = self.operation.apply(item).relieve();
+            let target_monkey = self.target_monkey(new_item);
+            result.push((new_item, target_monkey));
+            self.inspected += 1;
         }
 
         result
@@ -177,7 +237,7 @@ impl Operation {
 }
 
 impl Operator {
-    fn apply(&self, a: i32, b: i32) -> i32 {
+    pub(crate) fn apply(&self, a: i32, b: i32) -> i32 {
         match self {
             Operator::Addition => a + b,
             Operator::Multiplication => a * b,
@@ -195,24 +255,70 @@ impl Operand {
 }
 
 impl FromStr for Operand {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "old" => Operand::Old,
-            n => Operand::Number(n.parse().unwrap()),
+            n => Operand::Number(parse_token(s, n)?),
         })
     }
 }
 
 impl FromStr for Operator {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "+" => Operator::Addition,
             "*" => Operator::Multiplication,
-            _ => panic!(),
+            _ => return Err(ParseError::new("Invalid operator")),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    #[test]
+    fn part1_example() {
+        let input = Day11::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::default().part1(&input), 10605);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day11::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::default().part2(&input), 2713310158);
+    }
+}
@@ -1,204 +1,130 @@
-use crate::common::get_input_lines;
-use std::collections::HashSet;
+use crate::common::Part;
+use crate::error::ParseError;
+use crate::geometry::Point2;
+use crate::graph::{self, NoPath, Path};
+use crate::grid::Grid;
+use crate::render::{Canvas, Color, Frames};
+use crate::solution::Solution;
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
-struct Pos(usize);
-
-#[derive(Copy, Clone)]
-struct Coord {
-    x: usize,
-    y: usize,
-}
+type Pos = Point2<usize>;
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
 struct Elevation(u8);
 
-struct Field {
-    width: usize,
-    height: usize,
-    squares: Vec<Elevation>,
+pub struct Field {
+    squares: Grid<Elevation>,
     start: Pos,
     end: Pos,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[allow(dead_code)]
-pub fn run() {
-    let field = Field::parse(&get_input_lines());
-
-    let result = field.find_shortest_path_length();
-    println!("Result (part 1): {result}");
-
-    let result = field.find_hiking_trail_length();
-    println!("Result (part 2): {result}");
-}
-
-impl Field {
-    fn parse(input: &Vec<String>) -> Field {
-        let width = input[0].len();
-        let mut squares = Vec::with_capacity(width * input.len());
-        let mut start = Pos::default();
-        let mut end = Pos::default();
-
-        for line in input {
-            if line.is_empty() {
-                continue;
-            }
-
-            if line.len() != width {
-                panic!("Invalid line width");
-            }
-
-            for c in line.chars() {
-                match c {
-                    'S' => start = Pos::from_index(squares.len()),
-                    'E' => end = Pos::from_index(squares.len()),
-                    _ => {}
-                }
+pub struct Day12;
 
-                squares.push(c.into());
-            }
-        }
+impl Solution for Day12 {
+    type Input = Field;
+    type Answer1 = Result<usize, NoPath>;
+    type Answer2 = Result<usize, NoPath>;
 
-        Field {
-            width,
-            height: squares.len() / width,
-            squares,
-            start,
-            end,
-        }
+    fn parse(&self, input: &str) -> Result<Field, ParseError> {
+        Field::parse(input)
     }
 
-    fn find_shortest_path_length(&self) -> usize {
-        // Dijkstra's algorithm, as in https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm
-
-        let mut dist = (0..self.squares.len())
-            .map(|_| usize::MAX)
-            .collect::<Vec<_>>();
-
-        let mut queue = (0..self.squares.len())
-            .map(|i| Pos::from_index(i))
-            .collect::<HashSet<_>>();
-
-        dist[self.start.index()] = 0;
+    fn part1(&self, field: &Field) -> Result<usize, NoPath> {
+        field.find_shortest_path().map(|path| path.cost)
+    }
 
-        while !queue.is_empty() {
-            let u = queue
-                .iter()
-                .min_by_key(|pos| dist[pos.index()])
-                .copied()
-                .unwrap();
+    fn part2(&self, field: &Field) -> Result<usize, NoPath> {
+        field.find_hiking_trail().map(|path| path.cost)
+    }
 
-            queue.remove(&u);
+    fn render(&self, field: &Field, part: Part, frames: &mut Frames) -> bool {
+        let path = match part {
+            Part::One => field.find_shortest_path().map(|path| path.nodes),
+            Part::Two => field.find_hiking_trail().map(|path| {
+                let mut nodes = path.nodes;
+                nodes.reverse();
+                nodes
+            }),
+        };
 
-            if u == self.end {
-                return dist[u.index()];
-            }
+        let Ok(path) = path else {
+            return false;
+        };
 
-            for v in self
-                .neighbors(u)
-                .filter(|v| self.elevation(u).can_move_to(self.elevation(*v)) && queue.contains(v))
-            {
-                let alt = dist[u.index()] + 1;
-                let vd = dist.get_mut(v.index()).unwrap();
-                *vd = alt.min(*vd);
-            }
+        for len in 1..=path.len() {
+            frames.push(|| field.draw(&path[..len]));
         }
 
-        panic!("No path found")
+        frames.finish(|| field.draw(&path));
+        true
     }
+}
 
-    fn find_hiking_trail_length(&self) -> usize {
-        let mut dist = (0..self.squares.len())
-            .map(|_| usize::MAX)
-            .collect::<Vec<_>>();
-
-        let mut queue = (0..self.squares.len())
-            .map(|i| Pos::from_index(i))
-            .collect::<HashSet<_>>();
-
-        dist[self.end.index()] = 0;
-        let mut min_dist = usize::MAX;
-
-        while !queue.is_empty() {
-            let u = queue
-                .iter()
-                .min_by_key(|pos| dist[pos.index()])
-                .copied()
-                .unwrap();
-
-            queue.remove(&u);
-
-            if dist[u.index()] == usize::MAX {
-                break;
-            }
-
-            if self.elevation(u) == Elevation(0) {
-                min_dist = min_dist.min(dist[u.index()]);
-            }
+impl Field {
+    fn parse(input: &str) -> Result<Field, ParseError> {
+        let squares = Grid::parse(input, |c| Ok((c, Elevation::try_from(c)?)))?;
 
-            for v in self
-                .neighbors(u)
-                .filter(|v| self.elevation(*v).can_move_to(self.elevation(u)) && queue.contains(v))
-            {
-                let alt = dist[u.index()] + 1;
-                let vd = dist.get_mut(v.index()).unwrap();
-                *vd = alt.min(*vd);
-            }
+        if squares.is_empty() {
+            return Err(ParseError::new("Empty map").at_line(1));
         }
 
-        min_dist
-    }
+        let start = squares.position(|&(c, _)| c == 'S');
+        let end = squares.position(|&(c, _)| c == 'E');
 
-    fn coord(&self, pos: Pos) -> Coord {
-        Coord {
-            x: pos.index() % self.width,
-            y: pos.index() / self.width,
-        }
+        Ok(Field {
+            squares: squares.map(|&(_, elevation)| elevation),
+            start: start.ok_or_else(|| ParseError::new("Missing start position"))?,
+            end: end.ok_or_else(|| ParseError::new("Missing end position"))?,
+        })
     }
 
-    fn pos(&self, coord: Coord) -> Pos {
-        Pos(coord.x + coord.y * self.width)
+    fn find_shortest_path(&self) -> Result<Path<Pos, usize>, NoPath> {
+        let path = graph::bfs_to(
+            self.start,
+            |&u| {
+                self.neighbors(u)
+                    .filter(move |&v| self.squares[u].can_move_to(self.squares[v]))
+            },
+            |&u| u == self.end,
+        );
+
+        path.ok_or(NoPath)
     }
 
-    fn elevation(&self, pos: Pos) -> Elevation {
-        self.squares[pos.index()]
+    fn find_hiking_trail(&self) -> Result<Path<Pos, usize>, NoPath> {
+        // Walk down from the end, so that the first lowest square found is the closest one
+        let path = graph::bfs_to(
+            self.end,
+            |&u| {
+                self.neighbors(u)
+                    .filter(move |&v| self.squares[v].can_move_to(self.squares[u]))
+            },
+            |&u| self.squares[u] == Elevation(0),
+        );
+
+        path.ok_or(NoPath)
     }
 
-    fn neighbor(&self, pos: Pos, dir: Direction) -> Option<Pos> {
-        let coord = self.coord(pos);
+    /// Draws the heightmap from green valleys to white summits, with the given path and the best signal location.
+    fn draw(&self, path: &[Pos]) -> Canvas {
+        let mut canvas = Canvas::from_grid(&self.squares, Color::BLACK, |elevation| {
+            let t = elevation.0 as f64 / (b'z' - b'a') as f64;
 
-        let ok = match dir {
-            Direction::Up if coord.y > 0 => true,
-            Direction::Down if coord.y < self.height - 1 => true,
-            Direction::Left if coord.x > 0 => true,
-            Direction::Right if coord.x < self.width - 1 => true,
-            _ => false,
-        };
+            match t < 0.5 {
+                true => Color::GREEN.mix(Color::BROWN, t * 2.0),
+                false => Color::BROWN.mix(Color::WHITE, t * 2.0 - 1.0),
+            }
+        });
 
-        if ok {
-            Some(self.pos(coord.translate(dir)))
-        } else {
-            None
+        for position in path {
+            canvas.set(position.x, position.y, Color::RED);
         }
+
+        canvas.set(self.end.x, self.end.y, Color::YELLOW);
+        canvas
     }
 
-    fn neighbors(&self, square: Pos) -> impl Iterator<Item = Pos> + '_ {
-        [
-            Direction::Up,
-            Direction::Down,
-            Direction::Left,
-            Direction::Right,
-        ]
-        .into_iter()
-        .filter_map(move |dir| self.neighbor(square, dir))
+    fn neighbors(&self, position: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.squares.neighbors4_at(position)
     }
 }
 
@@ -208,44 +134,47 @@ impl Elevation {
     }
 }
 
-impl Default for Pos {
-    fn default() -> Self {
-        Pos(0)
-    }
-}
+impl TryFrom<char> for Elevation {
+    type Error = ParseError;
 
-impl Pos {
-    fn from_index(index: usize) -> Pos {
-        Pos(index)
-    }
-
-    fn index(&self) -> usize {
-        self.0
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Ok(match c {
+            'a'..='z' => Elevation((c as u32 - 'a' as u32) as u8),
+            'S' => Elevation(0),
+            'E' => Elevation(b'z' - b'a'),
+            _ => return Err(ParseError::new(format!("Invalid elevation: {c}"))),
+        })
     }
 }
 
-impl Coord {
-    fn new(x: usize, y: usize) -> Coord {
-        Coord { x, y }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    #[test]
+    fn part1_example() {
+        let input = Day12.parse(EXAMPLE).unwrap();
+        assert_eq!(Day12.part1(&input), Ok(31));
     }
 
-    fn translate(&self, dir: Direction) -> Coord {
-        match dir {
-            Direction::Up => Coord::new(self.x, self.y - 1),
-            Direction::Down => Coord::new(self.x, self.y + 1),
-            Direction::Left => Coord::new(self.x - 1, self.y),
-            Direction::Right => Coord::new(self.x + 1, self.y),
-        }
+    #[test]
+    fn part2_example() {
+        let input = Day12.parse(EXAMPLE).unwrap();
+        assert_eq!(Day12.part2(&input), Ok(29));
     }
-}
 
-impl From<char> for Elevation {
-    fn from(c: char) -> Self {
-        match c {
-            'a'..='z' => Elevation((c as u32 - 'a' as u32) as u8),
-            'S' => Elevation(0),
-            'E' => Elevation('z' as u8 - 'a' as u8),
-            _ => panic!("Invalid elevation: {c}"),
-        }
+    #[test]
+    fn unreachable_end() {
+        // The end is too high to be climbed to, and the start is the only lowest square
+        let input = Day12.parse("SbE").unwrap();
+        assert_eq!(Day12.part1(&input), Err(NoPath));
+        assert_eq!(Day12.part2(&input), Err(NoPath));
     }
 }
@@ -1,27 +1,34 @@
-use crate::common::get_input_lines;
+use crate::error::{parse_lines, parse_token, ParseError};
+use crate::solution::Solution;
+use crate::trace;
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 #[derive(Clone)]
-enum Value {
+pub enum Value {
     Number(i32),
     List(Vec<Value>),
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let values = get_input_lines()
-        .iter()
-        .filter(|i| !i.is_empty())
-        .map(|i| i.parse().unwrap())
-        .collect::<Vec<Value>>();
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Value>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let result = part1(&values);
-    println!("Result (part 1): {result}");
+    fn parse(&self, input: &str) -> Result<Vec<Value>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, values: &Vec<Value>) -> usize {
+        part1(values)
+    }
 
-    let result = part2(&values);
-    println!("Result (part 2): {result}");
+    fn part2(&self, values: &Vec<Value>) -> usize {
+        part2(values)
+    }
 }
 
 fn part1(values: &[Value]) -> usize {
@@ -31,9 +38,7 @@ fn part1(values: &[Value]) -> usize {
         let a = &values[2 * i];
         let b = &values[2 * i + 1];
 
-        println!("{}", a);
-        println!("{}", b);
-        println!();
+        trace!("{a}\n{b}\n");
 
         if a <= b {
             result += i + 1;
@@ -44,7 +49,7 @@ fn part1(values: &[Value]) -> usize {
 }
 
 fn part2(values: &[Value]) -> usize {
-    let mut values = values.iter().cloned().collect::<Vec<_>>();
+    let mut values = values.to_vec();
 
     let dividers = ["[[2]]", "[[6]]"]
         .iter()
@@ -57,36 +62,48 @@ fn part2(values: &[Value]) -> usize {
     dividers
         .iter()
         .map(|d| values.binary_search(d).unwrap() + 1)
-        .fold(1, |a, i| a * i)
+        .product()
 }
 
 impl FromStr for Value {
-    type Err = ();
+    type Err = ParseError;
 
-    fn from_str(mut s: &str) -> Result<Self, Self::Err> {
-        fn consume(mut s: &mut &str) -> Value {
-            if s.chars().next().unwrap() == '[' {
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        fn consume(line: &str, s: &mut &str) -> Result<Value, ParseError> {
+            if s.starts_with('[') {
                 *s = &s[1..];
                 let mut items = vec![];
                 loop {
-                    match s.chars().next().unwrap() {
-                        ']' => {
+                    match s.chars().next() {
+                        Some(']') => {
                             *s = &s[1..];
-                            return Value::List(items);
+                            return Ok(Value::List(items));
                         }
-                        ',' => *s = &s[1..],
-                        _ => items.push(consume(&mut s)),
+                        Some(',') => *s = &s[1..],
+                        Some(_) => items.push(consume(line, s)?),
+                        None => return Err(ParseError::at(line, s, "Expected ']'")),
                     }
                 }
             } else {
                 let len = s.find(|c: char| !c.is_numeric()).unwrap_or(s.len());
-                let result = Value::Number(s[..len].parse().unwrap());
+                if len == 0 {
+                    return Err(ParseError::at(line, s, "Expected a number or a list"));
+                }
+
+                let result = Value::Number(parse_token(line, &s[..len])?);
                 *s = &s[len..];
-                result
+                Ok(result)
             }
         }
 
-        Ok(consume(&mut s))
+        let mut s = line;
+        let value = consume(line, &mut s)?;
+
+        if !s.is_empty() {
+            return Err(ParseError::at(line, s, "Unexpected trailing characters"));
+        }
+
+        Ok(value)
     }
 }
 
@@ -143,3 +160,45 @@ impl Display for Value {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+    #[test]
+    fn part1_example() {
+        let input = Day13.parse(EXAMPLE).unwrap();
+        assert_eq!(Day13.part1(&input), 13);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day13.parse(EXAMPLE).unwrap();
+        assert_eq!(Day13.part2(&input), 140);
+    }
+}
@@ -1,17 +1,18 @@
-use crate::common::get_input_lines;
+use crate::common::Part;
+use crate::error::{parse_lines, ParseError};
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::render::{Canvas, Color, Frames};
+use crate::solution::Solution;
+use crate::trace;
 use std::fmt::{Display, Formatter};
-use std::ops::RangeInclusive;
 use std::str::FromStr;
 
 const SAND_ORIGIN: Point = Point::new(500, 0);
 
-#[derive(Copy, Clone)]
-struct Point {
-    x: usize,
-    y: usize,
-}
+type Point = Point2<isize>;
 
-struct Line(Vec<Point>);
+pub struct Line(Vec<Point>);
 
 #[derive(Copy, Clone)]
 struct Segment(Point, Point);
@@ -28,28 +29,47 @@ enum Tile {
 }
 
 struct Cave {
-    tiles: Vec<Tile>,
+    tiles: Grid<Tile>,
     bounds: Bounds,
     floor: bool,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let lines = get_input_lines()
-        .iter()
-        .filter(|i| !i.is_empty())
-        .map(|i| i.parse().unwrap())
-        .collect::<Vec<Line>>();
-
-    let mut cave = Cave::new(&lines, false);
-    let result = simulate(&mut cave);
-    println!("{cave}");
-    println!("Result (part 1): {result}");
-
-    let mut cave = Cave::new(&lines, true);
-    let result = simulate(&mut cave);
-    println!("{cave}");
-    println!("Result (part 2): {result}");
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Line>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Line>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, lines: &Vec<Line>) -> usize {
+        let mut cave = Cave::new(lines, false);
+        let result = simulate(&mut cave);
+        trace!("{cave}");
+        result
+    }
+
+    fn part2(&self, lines: &Vec<Line>) -> usize {
+        let mut cave = Cave::new(lines, true);
+        let result = simulate(&mut cave);
+        trace!("{cave}");
+        result
+    }
+
+    fn render(&self, lines: &Vec<Line>, part: Part, frames: &mut Frames) -> bool {
+        let mut cave = Cave::new(lines, part == Part::Two);
+        frames.push(|| cave.draw());
+
+        while cave.add_unit_of_sand() {
+            frames.push(|| cave.draw());
+        }
+
+        frames.finish(|| cave.draw());
+        true
+    }
 }
 
 fn simulate(cave: &mut Cave) -> usize {
@@ -67,10 +87,23 @@ fn simulate(cave: &mut Cave) -> usize {
 }
 
 impl FromStr for Line {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let points = s.split(" -> ").map(|i| i.parse().unwrap()).collect();
+        let mut points: Vec<Point> = vec![];
+
+        for token in s.split(" -> ") {
+            let point = token.parse::<Point>().map_err(|e| e.within(s, token))?;
+
+            if let Some(prev) = points.last() {
+                if prev.x != point.x && prev.y != point.y {
+                    return Err(ParseError::at(s, token, "Diagonal lines are not supported"));
+                }
+            }
+
+            points.push(point);
+        }
+
         Ok(Line(points))
     }
 }
@@ -87,27 +120,8 @@ impl Segment {
     }
 
     fn points(&self) -> impl Iterator<Item = Point> + '_ {
-        let (min, len, horiz);
-
-        if self.0.x == self.1.x {
-            min = self.0.y.min(self.1.y);
-            len = self.0.y.abs_diff(self.1.y) + 1;
-            horiz = false;
-        } else if self.0.y == self.1.y {
-            min = self.0.x.min(self.1.x);
-            len = self.0.x.abs_diff(self.1.x) + 1;
-            horiz = true;
-        } else {
-            panic!()
-        }
-
-        return (0..len).map(move |i| {
-            if horiz {
-                Point::new(min + i, self.0.y)
-            } else {
-                Point::new(self.0.x, min + i)
-            }
-        });
+        let step = (self.1 - self.0).signum();
+        (0..=self.0.chebyshev(self.1)).map(move |i| self.0 + step * i)
     }
 }
 
@@ -127,38 +141,23 @@ impl Bounds {
     }
 
     fn extend(&self, p: Point) -> Bounds {
-        Bounds::new(
-            Point::new(self.0.x.min(p.x), self.0.y.min(p.y)),
-            Point::new(self.1.x.max(p.x), self.1.y.max(p.y)),
-        )
+        Bounds::new(self.0.min(p), self.1.max(p))
     }
 
     fn width(&self) -> usize {
-        self.1.x - self.0.x + 1
+        (self.1.x - self.0.x + 1) as usize
     }
 
     fn height(&self) -> usize {
-        self.1.y - self.0.y + 1
-    }
-
-    fn range_x(&self) -> RangeInclusive<usize> {
-        self.0.x..=self.1.x
-    }
-
-    fn range_y(&self) -> RangeInclusive<usize> {
-        self.0.y..=self.1.y
-    }
-
-    fn contains(&self, p: Point) -> bool {
-        self.range_x().contains(&p.x) && self.range_y().contains(&p.y)
+        (self.1.y - self.0.y + 1) as usize
     }
 }
 
 impl Default for Bounds {
     fn default() -> Self {
         Bounds::new(
-            Point::new(usize::MAX, usize::MAX),
-            Point::new(usize::MIN, usize::MIN),
+            Point::new(isize::MAX, isize::MAX),
+            Point::new(isize::MIN, isize::MIN),
         )
     }
 }
@@ -168,12 +167,12 @@ impl Cave {
         let mut bounds = Bounds::from_lines(lines).extend(SAND_ORIGIN);
 
         if floor {
-            bounds = bounds.extend(bounds.1.delta(0, 1));
-            bounds = bounds.extend(SAND_ORIGIN.delta(bounds.height() as isize, 0));
-            bounds = bounds.extend(SAND_ORIGIN.delta(-(bounds.height() as isize), 0));
+            bounds = bounds.extend(bounds.1 + Point::new(0, 1));
+            bounds = bounds.extend(SAND_ORIGIN + Point::new(bounds.height() as isize, 0));
+            bounds = bounds.extend(SAND_ORIGIN - Point::new(bounds.height() as isize, 0));
         }
 
-        let tiles = vec![Tile::Empty; bounds.width() * bounds.height()];
+        let tiles = Grid::new(bounds.width(), bounds.height(), Tile::Empty);
         let mut cave = Cave {
             tiles,
             bounds,
@@ -195,17 +194,16 @@ impl Cave {
         }
     }
 
-    fn to_index(&self, p: Point) -> Option<usize> {
-        if self.bounds.contains(p) {
-            Some((p.y - self.bounds.0.y) * self.bounds.width() + p.x - self.bounds.0.x)
-        } else {
-            None
-        }
+    /// Converts a point to a position in the tiles grid.
+    fn to_pos(&self, p: Point) -> Point {
+        p - self.bounds.0
     }
 
     fn get(&self, p: Point) -> Tile {
-        if let Some(index) = self.to_index(p) {
-            self.tiles[index]
+        let Point2 { x, y } = self.to_pos(p);
+
+        if let Some(&tile) = self.tiles.get(x, y) {
+            tile
         } else if self.floor {
             Tile::Rock
         } else {
@@ -214,8 +212,8 @@ impl Cave {
     }
 
     fn set(&mut self, p: Point, t: Tile) {
-        let index = self.to_index(p);
-        self.tiles[index.unwrap()] = t;
+        let Point2 { x, y } = self.to_pos(p);
+        *self.tiles.get_mut(x, y).unwrap() = t;
     }
 
     fn add_unit_of_sand(&mut self) -> bool {
@@ -226,7 +224,7 @@ impl Cave {
         }
 
         'down: loop {
-            let next = [pt.delta(0, 1), pt.delta(-1, 1), pt.delta(1, 1)];
+            let next = [(0, 1), (-1, 1), (1, 1)].map(|(x, y)| pt + Point::new(x, y));
 
             for pos in next {
                 match self.get(pos) {
@@ -249,16 +247,28 @@ impl Cave {
     }
 }
 
-impl Display for Cave {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for y in self.bounds.range_y() {
-            for x in self.bounds.range_x() {
-                write!(f, "{}", self.get(Point::new(x, y)))?;
-            }
-            writeln!(f, "")?;
+impl Cave {
+    fn draw(&self) -> Canvas {
+        let mut canvas = Canvas::from_grid(&self.tiles, Color::BLACK, |tile| match tile {
+            Tile::Void | Tile::Empty => Color::BLACK,
+            Tile::Rock => Color::GRAY,
+            Tile::Sand => Color::SAND,
+        });
+
+        let origin = self.to_pos(SAND_ORIGIN);
+        if self.get(SAND_ORIGIN) == Tile::Empty {
+            canvas.set(origin.x, origin.y, Color::YELLOW);
         }
 
-        Ok(())
+        canvas.set_focus(origin.x as usize, origin.y as usize);
+
+        canvas
+    }
+}
+
+impl Display for Cave {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tiles)
     }
 }
 
@@ -273,35 +283,45 @@ impl Display for Tile {
     }
 }
 
-impl Point {
-    const fn new(x: usize, y: usize) -> Point {
-        Point { x, y }
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn delta(&self, x: isize, y: isize) -> Point {
-        Self::new(
-            (self.x as isize + x) as usize,
-            (self.y as isize + y) as usize,
-        )
+    const EXAMPLE: &str = "\
+498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn part1_example() {
+        let input = Day14.parse(EXAMPLE).unwrap();
+        assert_eq!(Day14.part1(&input), 24);
     }
-}
 
-impl PartialEq for Point {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
+    #[test]
+    fn part2_example() {
+        let input = Day14.parse(EXAMPLE).unwrap();
+        assert_eq!(Day14.part2(&input), 93);
     }
-}
 
-impl FromStr for Point {
-    type Err = ();
+    #[test]
+    fn render_example() {
+        let input = Day14.parse(EXAMPLE).unwrap();
+        let mut last = None;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split(',');
-        let x = iter.next().unwrap().parse().unwrap();
-        let y = iter.next().unwrap().parse().unwrap();
-        if iter.next().is_some() {
-            panic!();
-        }
-        Ok(Point { x, y })
+        let mut frames = Frames::new(1, |_, canvas: &Canvas| {
+            last = Some(canvas.clone());
+            Ok(())
+        });
+
+        assert!(Day14.render(&input, Part::One, &mut frames));
+        assert_eq!(frames.into_result().unwrap(), 25);
+
+        let last = last.unwrap();
+        let sand = (0..last.height())
+            .flat_map(|y| (0..last.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| last.get(x, y) == Color::SAND)
+            .count();
+
+        assert_eq!(sand, 24);
     }
 }
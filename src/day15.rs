@@ -1,19 +1,16 @@
-use crate::common::get_input_lines;
-use lazy_static::lazy_static;
-use regex::Regex;
+use crate::common::Part;
+use crate::error::{parse_lines, ParseError};
+use crate::geometry::Point2;
+use crate::interval::IntervalSet;
+use crate::params;
+use crate::parse::scan;
+use crate::solution::Solution;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 
-#[derive(Copy, Clone)]
-struct Point {
-    x: i64,
-    y: i64,
-}
-
-#[derive(Copy, Clone)]
-struct Bounds(Point, Point);
+type Point = Point2<i64>;
 
-struct Report {
+pub struct Report {
     items: Vec<ReportItem>,
 }
 
@@ -22,127 +19,134 @@ struct ReportItem {
     beacon: Point,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let items = get_input_lines()
-        .iter()
-        .filter(|i| !i.is_empty())
-        .map(|i| i.parse().unwrap())
-        .collect::<Vec<ReportItem>>();
+pub struct Day15 {
+    /// The row inspected in part 1.
+    pub row: i64,
+    /// The maximum coordinate of the search space in part 2.
+    pub search_space: i64,
+    /// The factor applied to the x coordinate of the distress beacon to compute its tuning frequency.
+    pub tuning_multiplier: i64,
+}
 
-    let report = Report { items };
+impl Default for Day15 {
+    fn default() -> Self {
+        Day15 {
+            row: 2_000_000,
+            search_space: 4_000_000,
+            tuning_multiplier: 4_000_000,
+        }
+    }
+}
 
-    let result = part1(&report, 2_000_000);
-    println!("Result (part 1): {result}");
+impl Solution for Day15 {
+    type Input = Report;
+    type Answer1 = usize;
+    type Answer2 = i64;
 
-    let result = part2(&report, 4_000_000);
-    println!("Result (part 2): {result}");
-}
+    fn parse(&self, input: &str) -> Result<Report, ParseError> {
+        let items = parse_lines::<ReportItem>(input)?;
 
-fn part1(report: &Report, line_index: i64) -> usize {
-    let (min, max) = report.coverage_x_bounds().into_inner();
+        if items.is_empty() {
+            return Err(ParseError::new("Empty report"));
+        }
 
-    let mut coverage = vec![false; (max - min + 1) as usize];
+        Ok(Report { items })
+    }
 
-    for item in report.items.iter() {
-        if let Some(range) = item.coverage_x_bounds_at_y(line_index) {
-            for x in range {
-                coverage[(x - min) as usize] = true;
+    fn part1(&self, report: &Report) -> usize {
+        part1(report, self.row)
+    }
+
+    fn part2(&self, report: &Report) -> i64 {
+        part2(report, self.search_space, self.tuning_multiplier)
+    }
+
+    fn reference(&self, report: &Report, part: Part) -> Option<String> {
+        Some(match part {
+            Part::One => reference_part1(report, self.row).to_string(),
+            Part::Two => {
+                reference_part2(report, self.search_space, self.tuning_multiplier).to_string()
             }
-        }
+        })
     }
 
+    params!(row, search_space, tuning_multiplier);
+}
+
+fn part1(report: &Report, line_index: i64) -> usize {
+    let mut coverage = report.coverage_at_y(line_index);
+
     for item in report.items.iter() {
         if item.beacon.y == line_index {
-            coverage[(item.beacon.x - min) as usize] = false;
+            coverage.remove(item.beacon.x..=item.beacon.x);
         }
     }
 
-    coverage.into_iter().filter(|&i| i).count()
+    coverage.len() as usize
 }
 
-fn part2(report: &Report, search_space: usize) -> usize {
-    let coverage_range = 0..=(search_space as i64);
-    let mut coverages = vec![];
+fn part2(report: &Report, search_space: i64, tuning_multiplier: i64) -> i64 {
+    let search_space = 0..=search_space;
 
-    for y in coverage_range.clone() {
-        coverages.clear();
+    for y in search_space.clone() {
+        let uncovered = report.coverage_at_y(y).complement(search_space.clone());
 
-        for item in report.items.iter() {
-            if let Some(range) = item.coverage_x_bounds_at_y(y) {
-                if let Some(range) = intersect(&range, &coverage_range) {
-                    coverages.push(range);
-                }
-            }
-        }
-
-        'outer: for i in (0..coverages.len()).rev() {
-            for j in 0..i {
-                if let Some(merged) = union(&coverages[i], &coverages[j]) {
-                    coverages[j] = merged;
-                    coverages.remove(i);
-                    continue 'outer;
-                }
-            }
-        }
-
-        if coverages.len() == 2 {
-            let (&end, &start) = if coverages[0].end() < coverages[1].start() {
-                (coverages[0].end(), coverages[1].start())
-            } else {
-                (coverages[1].end(), coverages[0].start())
-            };
-
-            if start == end + 2 {
-                let x = end + 1;
-                return (x as usize) * 4_000_000 + (y as usize);
-            }
+        if let Some(x) = uncovered.first() {
+            return x * tuning_multiplier + y;
         }
     }
 
     panic!("Not found");
 }
 
-fn intersect(a: &RangeInclusive<i64>, b: &RangeInclusive<i64>) -> Option<RangeInclusive<i64>> {
-    if a.end() < b.start() || a.start() > b.end() {
-        None
-    } else {
-        Some(*a.start().max(b.start())..=*a.end().min(b.end()))
-    }
+/// Checks each position of the row against every sensor.
+fn reference_part1(report: &Report, y: i64) -> usize {
+    let min_x = report.items.iter().map(|i| i.sensor.x - i.distance()).min();
+    let max_x = report.items.iter().map(|i| i.sensor.x + i.distance()).max();
+
+    (min_x.unwrap()..=max_x.unwrap())
+        .map(|x| Point::new(x, y))
+        .filter(|&point| report.items.iter().any(|i| i.covers(point)))
+        .filter(|&point| report.items.iter().all(|i| i.beacon != point))
+        .count()
 }
 
-fn union(a: &RangeInclusive<i64>, b: &RangeInclusive<i64>) -> Option<RangeInclusive<i64>> {
-    if a.end() + 1 < *b.start() || *a.start() > b.end() + 1 {
-        None
-    } else {
-        Some(*a.start().min(b.start())..=*a.end().max(b.end()))
+/// Checks each position of the search space against every sensor.
+fn reference_part2(report: &Report, search_space: i64, tuning_multiplier: i64) -> i64 {
+    for y in 0..=search_space {
+        for x in 0..=search_space {
+            let point = Point::new(x, y);
+
+            if !report.items.iter().any(|i| i.covers(point)) {
+                return x * tuning_multiplier + y;
+            }
+        }
     }
+
+    panic!("Not found");
 }
 
 impl Report {
-    fn coverage_x_bounds(&self) -> RangeInclusive<i64> {
-        let mut result = self.items[0].coverage_x_bounds();
-        for item in self.items.iter().skip(1) {
-            let range = item.coverage_x_bounds();
-            result = *result.start().min(range.start())..=*result.end().max(range.end());
-        }
-        result
+    fn coverage_at_y(&self, y: i64) -> IntervalSet<i64> {
+        self.items
+            .iter()
+            .filter_map(|item| item.coverage_x_bounds_at_y(y))
+            .collect()
     }
 }
 
 impl ReportItem {
-    fn distance(&self) -> u64 {
-        self.sensor.distance(self.beacon)
+    fn distance(&self) -> i64 {
+        self.sensor.manhattan(self.beacon)
     }
 
-    fn coverage_x_bounds(&self) -> RangeInclusive<i64> {
-        let dist = self.distance() as i64;
-        (self.sensor.x - dist)..=(self.sensor.x + dist)
+    fn covers(&self, point: Point) -> bool {
+        self.sensor.manhattan(point) <= self.distance()
     }
 
     fn coverage_x_bounds_at_y(&self, y: i64) -> Option<RangeInclusive<i64>> {
-        let dist = self.distance() as i64;
-        let diff_y = y.abs_diff(self.sensor.y) as i64;
+        let dist = self.distance();
+        let diff_y = (y - self.sensor.y).abs();
 
         if diff_y <= dist {
             Some((self.sensor.x - dist + diff_y)..=(self.sensor.x + dist - diff_y))
@@ -153,31 +157,66 @@ impl ReportItem {
 }
 
 impl FromStr for ReportItem {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(
-                r"Sensor at x=([-0-9]+), y=([-0-9]+): closest beacon is at x=([-0-9]+), y=([-0-9]+)"
-            )
-            .unwrap();
-        }
-
-        let cap = RE.captures(s).unwrap();
+        let mut fields = scan(s, "Sensor at x={}, y={}: closest beacon is at x={}, y={}")?;
 
         Ok(Self {
-            sensor: Point::new(cap[1].parse().unwrap(), cap[2].parse().unwrap()),
-            beacon: Point::new(cap[3].parse().unwrap(), cap[4].parse().unwrap()),
+            sensor: Point::new(fields.parse()?, fields.parse()?),
+            beacon: Point::new(fields.parse()?, fields.parse()?),
         })
     }
 }
 
-impl Point {
-    fn new(x: i64, y: i64) -> Point {
-        Point { x, y }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOLUTION: Day15 = Day15 {
+        row: 10,
+        search_space: 20,
+        tuning_multiplier: 4_000_000,
+    };
+
+    const EXAMPLE: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    #[test]
+    fn part1_example() {
+        let input = SOLUTION.parse(EXAMPLE).unwrap();
+        assert_eq!(SOLUTION.part1(&input), 26);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = SOLUTION.parse(EXAMPLE).unwrap();
+        assert_eq!(SOLUTION.part2(&input), 56000011);
     }
 
-    fn distance(&self, p: Point) -> u64 {
-        self.x.abs_diff(p.x) + self.y.abs_diff(p.y)
+    #[test]
+    fn reference_example() {
+        let input = SOLUTION.parse(EXAMPLE).unwrap();
+        assert_eq!(
+            SOLUTION.reference(&input, Part::One),
+            Some("26".to_string())
+        );
+        assert_eq!(
+            SOLUTION.reference(&input, Part::Two),
+            Some("56000011".to_string())
+        );
     }
 }
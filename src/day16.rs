@@ -1,6 +1,11 @@
-use crate::common::get_input_lines;
-use regex::Regex;
-use std::collections::HashSet;
+use crate::common::Part;
+use crate::error::ParseError;
+use crate::graph::{self, Paths};
+use crate::params;
+use crate::parse::scan_line;
+use crate::solution::Solution;
+use crate::trace;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
@@ -12,8 +17,10 @@ struct Valve {
     tunnels: Vec<usize>,
 }
 
-struct Layout {
+pub struct Layout {
     valves: Vec<Valve>,
+    /// The number of minutes it takes to walk from each valve to the others.
+    distances: Vec<Paths<usize, usize>>,
 }
 
 #[derive(Eq, PartialEq, Hash, Clone)]
@@ -31,47 +38,99 @@ struct Status2 {
     valves: Rc<Vec<bool>>,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let layout = Layout::parse(get_input_lines());
+/// Tries every action of every actor at each minute, without relying on the distances between the valves.
+struct Reference<'a> {
+    layout: &'a Layout,
+    /// The bit of each valve with a flow rate, in the set of opened valves.
+    bits: Vec<Option<u32>>,
+    best: HashMap<(usize, Vec<usize>, u64), usize>,
+}
 
-    let result = part1(&layout);
-    println!("Result (part 1): {result}");
+pub struct Day16 {
+    /// The time before the volcano erupts in part 1.
+    pub minutes: usize,
+    /// The time left in part 2, after teaching the elephant.
+    pub minutes_with_elephant: usize,
+}
 
-    let result = part2(&layout);
-    println!("Result (part 2): {result}");
+impl Default for Day16 {
+    fn default() -> Self {
+        Day16 {
+            minutes: 30,
+            minutes_with_elephant: 26,
+        }
+    }
 }
 
-fn part1(layout: &Layout) -> usize {
-    const TOTAL_MINUTES: usize = 30;
+impl Solution for Day16 {
+    type Input = Layout;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Layout, ParseError> {
+        Layout::parse(input)
+    }
+
+    fn part1(&self, layout: &Layout) -> usize {
+        part1(layout, self.minutes)
+    }
+
+    fn part2(&self, layout: &Layout) -> usize {
+        part2(layout, self.minutes_with_elephant)
+    }
+
+    /// The opened valves are kept in a bit set, so there is no reference beyond 64 valves with a flow rate.
+    fn reference(&self, layout: &Layout, part: Part) -> Option<String> {
+        if layout.valves.iter().filter(|i| i.flow_rate > 0).count() > 64 {
+            return None;
+        }
+
+        let answer = match part {
+            Part::One => Reference::new(layout).best_pressure(self.minutes, 1),
+            Part::Two => Reference::new(layout).best_pressure(self.minutes_with_elephant, 2),
+        };
+
+        Some(answer.to_string())
+    }
+
+    params!(minutes, minutes_with_elephant);
+}
 
+fn part1(layout: &Layout, minutes: usize) -> usize {
     let mut statuses = HashSet::new();
     statuses.insert(layout.initial_status());
 
-    for minute in 0..TOTAL_MINUTES {
-        let minutes_left = TOTAL_MINUTES - minute;
+    let mut max_score = 0;
+
+    for minute in 0..minutes {
+        let minutes_left = minutes - minute;
 
         let prev_statuses = statuses.iter().cloned().collect::<Vec<_>>();
         statuses.clear();
 
         for current_status in prev_statuses {
-            statuses.extend(current_status.possible_moves(layout, minutes_left));
+            if current_status.potential_score(layout, minutes_left) <= max_score {
+                continue;
+            }
+
+            for next_status in current_status.possible_moves(layout, minutes_left) {
+                max_score = max_score.max(next_status.score);
+                statuses.insert(next_status);
+            }
         }
     }
 
-    statuses.iter().map(|i| i.score).max().unwrap()
+    max_score
 }
 
-fn part2(layout: &Layout) -> usize {
-    const TOTAL_MINUTES: usize = 26;
-
+fn part2(layout: &Layout, minutes: usize) -> usize {
     let mut statuses = HashSet::new();
     statuses.insert(layout.initial_status2());
 
     let mut max_score = 0;
 
-    for minute in 0..TOTAL_MINUTES {
-        let minutes_left = TOTAL_MINUTES - minute;
+    for minute in 0..minutes {
+        let minutes_left = minutes - minute;
 
         let prev_statuses = statuses.iter().cloned().collect::<Vec<_>>();
         statuses.clear();
@@ -92,7 +151,7 @@ fn part2(layout: &Layout) -> usize {
             }
         }
 
-        println!(
+        trace!(
             "Minute {}, score {}, count {}, potential {}",
             minute + 1,
             max_score,
@@ -105,29 +164,44 @@ fn part2(layout: &Layout) -> usize {
 }
 
 impl Layout {
-    fn parse(input: Vec<String>) -> Layout {
-        let re =
-            Regex::new(r"Valve (\w+) has flow rate=(\d+); tunnels? leads? to valves? ([\w, ]+)")
-                .unwrap();
+    fn parse(input: &str) -> Result<Layout, ParseError> {
+        let mut layout = Layout {
+            valves: vec![],
+            distances: vec![],
+        };
 
-        let mut layout = Layout { valves: vec![] };
-
-        for line in input {
+        for (index, line) in input.lines().enumerate() {
             if line.is_empty() {
                 continue;
             }
 
-            let captures = re.captures(&line).unwrap();
-            let valve = layout.get_or_create_valve(&captures[1]);
-            layout.valves[valve].flow_rate = captures[2].parse().unwrap();
+            let pattern = "Valve {} has flow rate={}; {} {} to {} {}";
 
-            for path in captures[3].split(", ") {
-                let path = layout.get_or_create_valve(path);
-                layout.valves[valve].tunnels.push(path);
-            }
+            scan_line((index, line), pattern, |mut fields| {
+                let valve = layout.get_or_create_valve(fields.text());
+                layout.valves[valve].flow_rate = fields.parse()?;
+
+                // Either "tunnel leads to valve" or "tunnels lead to valves"
+                fields.skip(3);
+
+                for path in fields.text().split(", ") {
+                    let path = layout.get_or_create_valve(path);
+                    layout.valves[valve].tunnels.push(path);
+                }
+
+                Ok(())
+            })?;
         }
 
-        layout
+        if !layout.valves.iter().any(|i| i.name == "AA") {
+            return Err(ParseError::new("Missing valve AA"));
+        }
+
+        layout.distances = (0..layout.valves.len())
+            .map(|i| graph::bfs(i, |&j| layout.valves[j].tunnels.iter().copied()))
+            .collect();
+
+        Ok(layout)
     }
 
     fn get_or_create_valve(&mut self, name: &str) -> usize {
@@ -144,6 +218,29 @@ impl Layout {
         self.valves.len() - 1
     }
 
+    /// Returns the pressure released if every closed valve could be opened as soon as the nearest actor
+    /// walks to it, which no actual moves can beat.
+    fn potential_flow(&self, valves: &[bool], positions: &[usize], minutes_left: usize) -> usize {
+        let mut flow = 0;
+
+        for (i, &open) in valves.iter().enumerate() {
+            if open {
+                continue;
+            }
+
+            let distance = positions
+                .iter()
+                .filter_map(|&position| self.distances[position].cost(&i))
+                .min();
+
+            if let Some(distance) = distance {
+                flow += minutes_left.saturating_sub(distance + 1) * self.valves[i].flow_rate;
+            }
+        }
+
+        flow
+    }
+
     fn initial_status(&self) -> Status {
         let mut valves = vec![false; self.valves.len()];
 
@@ -197,6 +294,10 @@ impl Status {
 
         moves
     }
+
+    fn potential_score(&self, layout: &Layout, minutes_left: usize) -> usize {
+        self.score + layout.potential_flow(&self.valves, &[self.position], minutes_left)
+    }
 }
 
 impl Status2 {
@@ -255,15 +356,8 @@ impl Status2 {
     }
 
     fn potential_score(&self, layout: &Layout, minutes_left: usize) -> usize {
-        let mut score = self.score;
-
-        for i in 0..self.valves.len() {
-            if !self.valves[i] {
-                score += minutes_left * layout.valves[i].flow_rate;
-            }
-        }
-
-        score
+        let positions = [self.position_a, self.position_b];
+        self.score + layout.potential_flow(&self.valves, &positions, minutes_left)
     }
 }
 
@@ -284,8 +378,141 @@ impl Hash for Status2 {
     }
 }
 
+impl<'a> Reference<'a> {
+    fn new(layout: &'a Layout) -> Reference<'a> {
+        let mut next_bit = 0..;
+
+        let bits = layout
+            .valves
+            .iter()
+            .map(|i| (i.flow_rate > 0).then(|| next_bit.next().unwrap()))
+            .collect();
+
+        Reference {
+            layout,
+            bits,
+            best: HashMap::new(),
+        }
+    }
+
+    fn best_pressure(&mut self, minutes: usize, actors: usize) -> usize {
+        let start = self.layout.valves.iter().position(|i| i.name == "AA");
+        self.released(minutes, vec![start.unwrap(); actors], 0)
+    }
+
+    /// Returns the most pressure which can be released in the time left, by actors at the given positions.
+    fn released(&mut self, minutes_left: usize, mut positions: Vec<usize>, opened: u64) -> usize {
+        if minutes_left == 0 {
+            return 0;
+        }
+
+        // The actors are interchangeable
+        positions.sort();
+        let key = (minutes_left, positions, opened);
+
+        if let Some(&best) = self.best.get(&key) {
+            return best;
+        }
+
+        // Each actor either stays, walks through a tunnel or opens its valve
+        let mut outcomes = vec![(vec![], opened, 0)];
+
+        for &position in &key.1 {
+            let valve = &self.layout.valves[position];
+            let mut next_outcomes = vec![];
+
+            for (moved, opened, pressure) in outcomes {
+                let destinations = valve.tunnels.iter().chain([&position]);
+
+                for &destination in destinations {
+                    let mut moved = moved.clone();
+                    moved.push(destination);
+                    next_outcomes.push((moved, opened, pressure));
+                }
+
+                match self.bits[position] {
+                    Some(bit) if opened & (1 << bit) == 0 => {
+                        let mut moved = moved;
+                        moved.push(position);
+                        let flow = valve.flow_rate * (minutes_left - 1);
+                        next_outcomes.push((moved, opened | (1 << bit), pressure + flow));
+                    }
+                    _ => {}
+                }
+            }
+
+            outcomes = next_outcomes;
+        }
+
+        let best = outcomes
+            .into_iter()
+            .map(|(moved, opened, pressure)| {
+                pressure + self.released(minutes_left - 1, moved, opened)
+            })
+            .max()
+            .unwrap();
+
+        self.best.insert(key, best);
+        best
+    }
+}
+
 impl Display for Valve {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::generate;
+
+    const EXAMPLE: &str = "\
+Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    #[test]
+    fn part1_example() {
+        let input = Day16::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day16::default().part1(&input), 1651);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day16::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day16::default().part2(&input), 1707);
+    }
+
+    #[test]
+    fn reference_example() {
+        let input = Day16::default().parse(EXAMPLE).unwrap();
+        let reference = |part| Day16::default().reference(&input, part);
+
+        assert_eq!(reference(Part::One), Some("1651".to_string()));
+        assert_eq!(reference(Part::Two), Some("1707".to_string()));
+    }
+
+    #[test]
+    fn part1_pruning() {
+        // Part 1 skips the statuses which cannot beat the best score so far, which cuts little in the example
+        for seed in 0..10 {
+            let input = generate(16, seed, 30).unwrap().input;
+            let layout = Day16::default().parse(&input).unwrap();
+
+            assert_eq!(
+                Day16::default().reference(&layout, Part::One),
+                Some(Day16::default().part1(&layout).to_string()),
+                "Seed {seed}"
+            );
+        }
+    }
+}
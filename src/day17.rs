@@ -1,6 +1,17 @@
-use crate::common::get_input_lines;
+use crate::common::Part;
+use crate::error::ParseError;
+use crate::graph;
+use crate::params;
+use crate::render::{Canvas, Color, Frames};
+use crate::solution::Solution;
+use crate::trace;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
+/// The most rocks which the reference drops, one by one. It gives no answer for more, such as the part 2 puzzle.
+const REFERENCE_ROCKS: usize = 100_000;
+
 #[derive(Copy, Clone)]
 enum Direction {
     Left,
@@ -8,9 +19,9 @@ enum Direction {
 }
 
 #[derive(Clone)]
-struct Pushes(Vec<Direction>);
+pub struct Pushes(Vec<Direction>);
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Default)]
 struct Line(u8);
 
 #[derive(Clone)]
@@ -30,81 +41,177 @@ struct Puzzle {
     push_count: usize,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let pushes = Pushes::parse(get_input_lines().iter().next().unwrap());
+pub struct Day17 {
+    /// The number of rocks dropped in part 1.
+    pub rocks: usize,
+    /// The number of rocks dropped in part 2.
+    pub many_rocks: usize,
+}
+
+impl Default for Day17 {
+    fn default() -> Self {
+        Day17 {
+            rocks: 2022,
+            many_rocks: 1_000_000_000_000,
+        }
+    }
+}
+
+impl Solution for Day17 {
+    type Input = Pushes;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Pushes, ParseError> {
+        let line = input.lines().next().unwrap_or_default();
+        Pushes::parse(line).map_err(|e| e.at_line(1))
+    }
+
+    fn part1(&self, pushes: &Pushes) -> usize {
+        part1(pushes, self.rocks)
+    }
+
+    fn part2(&self, pushes: &Pushes) -> usize {
+        part2(pushes, self.many_rocks)
+    }
+
+    fn reference(&self, pushes: &Pushes, part: Part) -> Option<String> {
+        let rocks = match part {
+            Part::One => self.rocks,
+            Part::Two => self.many_rocks,
+        };
+
+        if rocks > REFERENCE_ROCKS {
+            return None;
+        }
+
+        Some(reference_height(pushes, rocks).to_string())
+    }
+
+    fn render(&self, pushes: &Pushes, part: Part, frames: &mut Frames) -> bool {
+        if part == Part::Two {
+            return false;
+        }
+
+        let height = part1(pushes, self.rocks);
+        let mut puzzle = Puzzle::new(pushes.clone());
+        frames.push(|| puzzle.cave.draw(height));
+
+        while puzzle.block_count < self.rocks {
+            puzzle.drop_block();
+            frames.push(|| puzzle.cave.draw(height));
+        }
 
-    let result = part1(&pushes, 2022);
-    println!("Result (part 1): {result}");
+        frames.finish(|| puzzle.cave.draw(height));
+        true
+    }
 
-    let result = part2(&pushes, 1000000000000);
-    println!("Result (part 2): {result}");
+    params!(rocks, many_rocks);
 }
 
 fn part1(pushes: &Pushes, iterations: usize) -> usize {
     let mut puzzle = Puzzle::new(pushes.clone());
 
-    loop {
+    while puzzle.block_count < iterations {
         puzzle.drop_block();
-
-        if puzzle.block_count == iterations {
-            break;
-        }
     }
 
-    return puzzle.cave.height();
+    puzzle.cave.height()
 }
 
 fn part2(pushes: &Pushes, iterations: usize) -> usize {
     let mut puzzle = Puzzle::new(pushes.clone());
+    let mut seen = HashMap::new();
+    let mut depth = Cave::SURFACE_DEPTH;
 
-    #[derive(Copy, Clone, Default)]
-    struct Status {
-        count: usize,
-        height: usize,
-        blocks: usize,
-    }
+    while puzzle.block_count < iterations {
+        let state = (puzzle.current_combination(), puzzle.cave.surface(depth));
 
-    let mut combinations = vec![Status::default(); puzzle.combination_count()];
+        match seen.entry(state) {
+            // The same rock falls with the same jets onto the same surface, so the tower grows in cycles from there
+            Entry::Occupied(entry) => {
+                let (prev_blocks, prev_height) = *entry.get();
+                let cycle_blocks = puzzle.block_count - prev_blocks;
+                let cycle_height = puzzle.cave.height() - prev_height;
 
-    loop {
-        let status = &mut combinations[puzzle.current_combination()];
+                let needed_blocks = iterations - puzzle.block_count;
 
-        puzzle.drop_block();
+                for _ in 0..needed_blocks % cycle_blocks {
+                    puzzle.drop_block();
+                }
 
-        if puzzle.block_count == iterations {
-            return puzzle.cave.height();
+                return puzzle.cave.height() + needed_blocks / cycle_blocks * cycle_height;
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((puzzle.block_count, puzzle.cave.height()));
+            }
         }
 
-        status.count += 1;
+        let top = puzzle.cave.height();
+        let rest = puzzle.drop_block();
 
-        let prev_height = status.height;
-        status.height = puzzle.cave.height();
+        // The rock fell below the surface, where it could hit lines which the surface leaves out. The surfaces seen
+        // so far do not tell where the next rocks come to rest, so start over with a deeper one.
+        if rest + depth <= top {
+            depth = (2 * (top - rest)).min(Cave::MAX_SURFACE_DEPTH);
+            seen.clear();
+        }
+    }
 
-        let prev_blocks = status.blocks;
-        status.blocks = puzzle.block_count;
+    puzzle.cave.height()
+}
 
-        if status.count == 3 && status.height > prev_height {
-            let diff_height = status.height - prev_height;
-            let diff_blocks = status.blocks - prev_blocks;
+/// Drops every rock one by one, keeping the cells of the fallen rocks in a set, where `y` goes up.
+fn reference_height(pushes: &Pushes, rocks: usize) -> usize {
+    const SHAPES: [&[(i64, i64)]; Block::TYPE_COUNT] = [
+        &[(0, 0), (1, 0), (2, 0), (3, 0)],
+        &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+        &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+        &[(0, 0), (0, 1), (0, 2), (0, 3)],
+        &[(0, 0), (1, 0), (0, 1), (1, 1)],
+    ];
+
+    let fits = |cells: &HashSet<(i64, i64)>, shape: &[(i64, i64)], x: i64, y: i64| {
+        shape.iter().all(|&(dx, dy)| {
+            let cell = (x + dx, y + dy);
+            (0..7).contains(&cell.0) && cell.1 >= 0 && !cells.contains(&cell)
+        })
+    };
 
-            let needed_blocks = iterations - puzzle.block_count;
-            let additional_iterations = needed_blocks / diff_blocks;
+    let mut cells = HashSet::new();
+    let mut height = 0;
+    let mut push = 0;
 
-            let additional_blocks = additional_iterations * diff_blocks;
-            let additional_height = additional_iterations * diff_height;
+    for rock in 0..rocks {
+        let shape = SHAPES[rock % SHAPES.len()];
+        let (mut x, mut y) = (2, height + 3);
 
-            loop {
-                let blocks = puzzle.block_count + additional_blocks;
+        loop {
+            let dx = match pushes.get(push) {
+                Direction::Left => -1,
+                Direction::Right => 1,
+            };
 
-                if blocks == iterations {
-                    return puzzle.cave.height() + additional_height;
-                }
+            push += 1;
+
+            if fits(&cells, shape, x + dx, y) {
+                x += dx;
+            }
 
-                puzzle.drop_block();
+            if !fits(&cells, shape, x, y - 1) {
+                break;
             }
+
+            y -= 1;
+        }
+
+        for &(dx, dy) in shape {
+            cells.insert((x + dx, y + dy));
+            height = height.max(y + dy + 1);
         }
     }
+
+    height as usize
 }
 
 impl Puzzle {
@@ -117,7 +224,8 @@ impl Puzzle {
         }
     }
 
-    fn drop_block(&mut self) {
+    /// Drops the next rock, and returns the line where it comes to rest.
+    fn drop_block(&mut self) -> usize {
         let mut block = Block::new(self.block_count, self.cave.height() + 3);
         self.block_count += 1;
 
@@ -130,15 +238,11 @@ impl Puzzle {
                 block = fallen;
             } else {
                 self.cave.merge_block(&block);
-                break;
+                return block.height;
             }
         }
     }
 
-    fn combination_count(&self) -> usize {
-        Block::TYPE_COUNT * self.pushes.0.len()
-    }
-
     fn current_combination(&self) -> usize {
         let block_index = self.block_count % Block::TYPE_COUNT;
         let push_index = self.push_count % self.pushes.0.len();
@@ -147,18 +251,24 @@ impl Puzzle {
 }
 
 impl Pushes {
-    fn parse(value: &str) -> Pushes {
+    fn parse(value: &str) -> Result<Pushes, ParseError> {
         let mut result = Vec::with_capacity(value.len());
 
-        for b in value.bytes() {
-            result.push(match b {
-                b'<' => Direction::Left,
-                b'>' => Direction::Right,
-                _ => panic!(),
+        for (index, c) in value.chars().enumerate() {
+            result.push(match c {
+                '<' => Direction::Left,
+                '>' => Direction::Right,
+                _ => {
+                    return Err(ParseError::new(format!("Invalid jet: '{c}'")).at_column(index + 1))
+                }
             })
         }
 
-        Pushes(result)
+        if result.is_empty() {
+            return Err(ParseError::new("Empty jet pattern"));
+        }
+
+        Ok(Pushes(result))
     }
 
     fn get(&self, index: usize) -> Direction {
@@ -167,6 +277,14 @@ impl Pushes {
 }
 
 impl Cave {
+    /// The number of lines below the top which make up the surface at first. A rock can still fall deeper, down
+    /// a shaft which the jets leave open, and then the surface does not tell where it comes to rest.
+    const SURFACE_DEPTH: usize = 64;
+
+    /// The number of lines below the top which make up the deepest surface. Rocks which keep falling deeper stop
+    /// the tower from being seen in cycles, and then every rock is dropped.
+    const MAX_SURFACE_DEPTH: usize = 256;
+
     fn new() -> Cave {
         Cave { lines: vec![] }
     }
@@ -194,17 +312,79 @@ impl Cave {
         }
     }
 
+    /// Returns the empty cells which can be reached from above the tower, line by line from the top.
+    /// The rocks which come to rest within the surface fall in the same way onto caves which have the same surface.
+    ///
+    /// Only the top `depth` lines are explored: when the jets leave a column open down to the floor, the whole
+    /// surface would grow with each rock and never repeat.
+    fn surface(&self, depth: usize) -> Vec<Line> {
+        let top = self.height();
+        let bottom = top.saturating_sub(depth);
+        let is_empty = |x: usize, y: usize| !self.get(y).overlaps(Line::cell(x));
+
+        let reached = graph::bfs((0, top), |&(x, y): &(usize, usize)| {
+            [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ]
+            .into_iter()
+            .filter(|&(x, y)| x < 7 && (bottom..=top).contains(&y) && is_empty(x, y))
+        });
+
+        let mut lines = vec![];
+
+        for (&(x, y), _) in reached.iter() {
+            if lines.len() <= top - y {
+                lines.resize(top - y + 1, Line::default());
+            }
+
+            lines[top - y] = lines[top - y].merge(Line::cell(x));
+        }
+
+        lines
+    }
+
+    /// Draws the tower between the walls and above the floor, in a picture tall enough for `height` lines.
+    fn draw(&self, height: usize) -> Canvas {
+        let mut canvas = Canvas::new(9, height + 1, Color::BLACK);
+
+        for y in 0..=height {
+            canvas.set(0, y, Color::GRAY);
+            canvas.set(8, y, Color::GRAY);
+        }
+
+        for x in 0..9 {
+            canvas.set(x, height, Color::GRAY);
+        }
+
+        for (y, line) in self.lines.iter().enumerate() {
+            for x in (0..7).filter(|&x| line.overlaps(Line::cell(x))) {
+                canvas.set(x + 1, height - 1 - y, Color::WHITE);
+            }
+        }
+
+        canvas.set_focus(4, height - self.height());
+        canvas
+    }
+
     #[allow(dead_code)]
     fn print(&self) {
-        println!();
+        trace!();
         for line in self.lines.iter().rev() {
-            println!("|{}|", line);
+            trace!("|{}|", line);
         }
-        println!("+-------+");
+        trace!("+-------+");
     }
 }
 
 impl Line {
+    /// Returns the line with only the cell at `x` filled, from the left wall.
+    fn cell(x: usize) -> Line {
+        Line(1 << (6 - x))
+    }
+
     fn push(&self, dir: Direction) -> Line {
         Line(match dir {
             Direction::Left => {
@@ -237,16 +417,18 @@ impl Line {
     }
 }
 
-impl Default for Line {
-    fn default() -> Self {
-        Line(0)
-    }
-}
-
 impl Display for Line {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for i in 0..7 {
-            write!(f, "{}", if self.0 & (1 << 6 - i) != 0 { '#' } else { '.' })?
+            write!(
+                f,
+                "{}",
+                if self.0 & (1 << (6 - i)) != 0 {
+                    '#'
+                } else {
+                    '.'
+                }
+            )?
         }
         Ok(())
     }
@@ -328,9 +510,89 @@ impl Block {
             }
         }
 
-        return Ok(Block {
+        Ok(Block {
             lines: self.lines,
             height: self.height - 1,
-        });
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+>>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn part1_example() {
+        let input = Day17::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day17::default().part1(&input), 3068);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day17::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day17::default().part2(&input), 1514285714288);
+    }
+
+    #[test]
+    fn reference_example() {
+        let solution = Day17 {
+            many_rocks: 5000,
+            ..Default::default()
+        };
+
+        let input = solution.parse(EXAMPLE).unwrap();
+        assert_eq!(
+            solution.reference(&input, Part::One),
+            Some("3068".to_string())
+        );
+        assert_eq!(Day17::default().reference(&input, Part::Two), None);
+        assert_eq!(
+            solution.reference(&input, Part::Two),
+            Some(solution.part2(&input).to_string())
+        );
+    }
+
+    #[test]
+    fn single_jet_cycles() {
+        // These jets leave columns open down to the floor, which must not keep the surface from repeating
+        for jets in ["<", ">", "<<>", "<<<>"] {
+            let input = Day17::default().parse(jets).unwrap();
+            let solution = Day17 {
+                many_rocks: 5000,
+                ..Default::default()
+            };
+
+            assert_eq!(
+                solution.reference(&input, Part::Two),
+                Some(solution.part2(&input).to_string()),
+                "{jets}"
+            );
+
+            // The full count of rocks used to run until the tower reached it
+            Day17::default().part2(&input);
+        }
+    }
+
+    #[test]
+    fn deep_shaft() {
+        // These jets leave a shaft open where some rocks fall further than the first surface reaches
+        for jets in [">>><<>><<>>>><<><<", "<<><>><><><>>>>"] {
+            let input = Day17::default().parse(jets).unwrap();
+            let solution = Day17 {
+                many_rocks: 5000,
+                ..Default::default()
+            };
+
+            assert_eq!(
+                solution.reference(&input, Part::Two),
+                Some(solution.part2(&input).to_string()),
+                "{jets}"
+            );
+
+            Day17::default().part2(&input);
+        }
     }
 }
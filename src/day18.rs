@@ -1,12 +1,11 @@
-use crate::common::get_input_lines;
-use std::collections::{HashMap, HashSet, VecDeque};
-
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
-struct Coord {
-    x: i32,
-    y: i32,
-    z: i32,
-}
+use crate::error::{parse_lines, ParseError};
+use crate::geometry::Point3;
+use crate::graph;
+use crate::solution::Solution;
+use std::array;
+use std::collections::HashMap;
+
+type Coord = Point3<i32>;
 
 #[derive(Default, Clone)]
 struct Cube<T> {
@@ -29,24 +28,33 @@ enum Side {
 }
 
 #[derive(Clone)]
-struct Puzzle {
+pub struct Puzzle {
     cubes: HashMap<Coord, Cube<bool>>,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let puzzle = Puzzle::parse(get_input_lines());
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Puzzle;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::parse(input)
+    }
 
-    let result = part1(puzzle.clone());
-    println!("Result (part 1): {result}");
+    fn part1(&self, puzzle: &Puzzle) -> usize {
+        part1(puzzle.clone())
+    }
 
-    let result = part2(puzzle);
-    println!("Result (part 2): {result}");
+    fn part2(&self, puzzle: &Puzzle) -> usize {
+        part2(puzzle)
+    }
 }
 
 fn part1(mut puzzle: Puzzle) -> usize {
     for coord in puzzle.cubes.keys().copied().collect::<Vec<_>>() {
-        for adjacent in coord.adjacent() {
+        for adjacent in neighbors(coord) {
             if let Some(cube) = puzzle.cubes.get_mut(&adjacent.1) {
                 cube.set(&adjacent.0.opposite(), true)
             }
@@ -56,7 +64,7 @@ fn part1(mut puzzle: Puzzle) -> usize {
     puzzle.cubes.values().map(|i| i.count(|&j| !j)).sum()
 }
 
-fn part2(mut puzzle: Puzzle) -> usize {
+fn part2(puzzle: &Puzzle) -> usize {
     let min = Coord {
         x: puzzle.cubes.keys().map(|i| i.x - 1).min().unwrap(),
         y: puzzle.cubes.keys().map(|i| i.y - 1).min().unwrap(),
@@ -69,114 +77,53 @@ fn part2(mut puzzle: Puzzle) -> usize {
         z: puzzle.cubes.keys().map(|i| i.z + 1).max().unwrap(),
     };
 
-    let mut visited = HashSet::new();
-    let mut to_visit = VecDeque::from([min]);
-
-    while let Some(coord) = to_visit.pop_front() {
-        if !visited.insert(coord) {
-            continue;
-        }
-
-        for adjacent in coord.adjacent() {
-            let coord = adjacent.1;
-
-            if coord.x < min.x
-                || coord.y < min.y
-                || coord.z < min.z
-                || coord.x > max.x
-                || coord.y > max.y
-                || coord.z > max.z
-            {
-                continue;
-            }
-
-            if let Some(cube) = puzzle.cubes.get_mut(&coord) {
-                cube.set(&adjacent.0.opposite(), true)
-            } else {
-                to_visit.push_back(coord)
-            }
-        }
-    }
-
-    puzzle.cubes.values().map(|i| i.count(|&j| j)).sum()
+    // Flood the air around the droplet, within a box which leaves a gap on every side
+    let outside = graph::bfs(min, |&coord| {
+        coord
+            .neighbors6()
+            .into_iter()
+            .filter(|&i| i.min(min) == min && i.max(max) == max && !puzzle.cubes.contains_key(&i))
+    });
+
+    // Each exterior side touches exactly one outside coordinate
+    outside
+        .iter()
+        .flat_map(|(coord, _)| coord.neighbors6())
+        .filter(|i| puzzle.cubes.contains_key(i))
+        .count()
 }
 
 impl Puzzle {
-    fn parse(input: Vec<String>) -> Puzzle {
-        Puzzle {
-            cubes: input
-                .iter()
-                .map(|i| (Coord::parse(i), Cube::default()))
-                .collect(),
-        }
-    }
-}
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let coords = parse_lines::<Coord>(input)?;
 
-impl Coord {
-    fn parse(value: &str) -> Coord {
-        let mut coords = value.trim().split(",").map(|i| i.parse().unwrap());
-        Coord {
-            x: coords.next().unwrap(),
-            y: coords.next().unwrap(),
-            z: coords.next().unwrap(),
+        if coords.is_empty() {
+            return Err(ParseError::new("No cubes"));
         }
-    }
 
-    fn adjacent(&self) -> [(Side, Coord); 6] {
-        [
-            (
-                Side::Top,
-                Coord {
-                    x: self.x,
-                    y: self.y - 1,
-                    z: self.z,
-                },
-            ),
-            (
-                Side::Bottom,
-                Coord {
-                    x: self.x,
-                    y: self.y + 1,
-                    z: self.z,
-                },
-            ),
-            (
-                Side::Left,
-                Coord {
-                    x: self.x - 1,
-                    y: self.y,
-                    z: self.z,
-                },
-            ),
-            (
-                Side::Right,
-                Coord {
-                    x: self.x + 1,
-                    y: self.y,
-                    z: self.z,
-                },
-            ),
-            (
-                Side::Front,
-                Coord {
-                    x: self.x,
-                    y: self.y,
-                    z: self.z - 1,
-                },
-            ),
-            (
-                Side::Back,
-                Coord {
-                    x: self.x,
-                    y: self.y,
-                    z: self.z + 1,
-                },
-            ),
-        ]
+        Ok(Puzzle {
+            cubes: coords.into_iter().map(|i| (i, Cube::default())).collect(),
+        })
     }
 }
 
+/// Returns the adjacent coordinates, along with the side of the cube they touch.
+fn neighbors(coord: Coord) -> [(Side, Coord); 6] {
+    let neighbors = coord.neighbors6();
+    array::from_fn(|i| (Side::ALL[i], neighbors[i]))
+}
+
 impl Side {
+    /// The sides, in the same order as [`Point3::neighbors6`].
+    const ALL: [Side; 6] = [
+        Side::Top,
+        Side::Bottom,
+        Side::Left,
+        Side::Right,
+        Side::Front,
+        Side::Back,
+    ];
+
     fn opposite(&self) -> Side {
         match self {
             Side::Top => Side::Bottom,
@@ -231,3 +178,35 @@ impl<T> Cube<T> {
         self.values().iter().filter(|i| predicate(i)).count()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    #[test]
+    fn part1_example() {
+        let input = Day18.parse(EXAMPLE).unwrap();
+        assert_eq!(Day18.part1(&input), 64);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day18.parse(EXAMPLE).unwrap();
+        assert_eq!(Day18.part2(&input), 58);
+    }
+}
@@ -1,313 +1,355 @@
-use crate::common::get_input_lines;
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::ops::{Index, IndexMut};
+use crate::common::Part;
+use crate::error::{parse_lines, ParseError};
+use crate::params;
+use crate::parse::scan;
+use crate::solution::Solution;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 const ROBOT_TYPES: usize = 4;
 
-#[derive(Copy, Clone)]
-enum Res {
-    Ore = 0,
-    Clay = 1,
-    Obsidian = 2,
-    Geode = 3,
-}
+/// The robot types and resources are ore, clay, obsidian and geodes, in that order.
+const GEODE: usize = 3;
 
-struct Puzzle {
+pub struct Puzzle {
     blueprints: Vec<Blueprint>,
 }
 
 struct Blueprint {
     id: usize,
-    ore_robot_cost_in_ore: usize,
-    clay_robot_cost_in_ore: usize,
-    obsidian_robot_cost_in_ore: usize,
-    obsidian_robot_cost_in_clay: usize,
-    geode_robot_cost_in_ore: usize,
-    geode_robot_cost_in_obsidian: usize,
+    /// The cost of each robot type, in ore, clay and obsidian.
+    costs: [[usize; 3]; ROBOT_TYPES],
 }
 
-#[derive(Clone)]
-struct Simulation<'a> {
-    blueprint: &'a Blueprint,
-    resources: [ResourceStatus; ROBOT_TYPES],
-    next_robot: Res,
+pub struct Day19 {
+    /// The time available in part 1.
+    pub minutes: usize,
+    /// The time available in part 2.
+    pub long_minutes: usize,
+    /// The number of blueprints which remain in part 2.
+    pub long_blueprints: usize,
 }
 
-#[derive(Default, Copy, Clone)]
-struct ResourceStatus {
-    count: usize,
-    robots: usize,
-    pending_robots: usize,
+impl Default for Day19 {
+    fn default() -> Self {
+        Day19 {
+            minutes: 24,
+            long_minutes: 32,
+            long_blueprints: 3,
+        }
+    }
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let puzzle = Puzzle::parse(get_input_lines());
+impl Solution for Day19 {
+    type Input = Puzzle;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::parse(input)
+    }
+
+    fn part1(&self, puzzle: &Puzzle) -> usize {
+        part1(puzzle, self.minutes)
+    }
+
+    fn part2(&self, puzzle: &Puzzle) -> usize {
+        part2(puzzle, self.long_minutes, self.long_blueprints)
+    }
+
+    fn reference(&self, puzzle: &Puzzle, part: Part) -> Option<String> {
+        let answer = match part {
+            Part::One => puzzle
+                .blueprints
+                .iter()
+                .map(|bp| bp.id * reference_max_geodes(bp, self.minutes, true))
+                .sum::<usize>(),
+            Part::Two => puzzle
+                .blueprints
+                .iter()
+                .take(self.long_blueprints)
+                .map(|bp| reference_max_geodes(bp, self.long_minutes, true))
+                .product(),
+        };
 
-    let result = part1(&puzzle);
-    println!("Result (part 1): {result}");
+        Some(answer.to_string())
+    }
 
-    let result = part2(&puzzle);
-    println!("Result (part 2): {result}");
+    params!(minutes, long_minutes, long_blueprints);
 }
 
-fn part1(puzzle: &Puzzle) -> usize {
+fn part1(puzzle: &Puzzle, minutes: usize) -> usize {
     puzzle
         .blueprints
         .iter()
-        .map(|bp| bp.id * max_geodes(&bp, 24))
+        .map(|bp| bp.id * max_geodes(bp, minutes))
         .sum()
 }
 
-fn part2(puzzle: &Puzzle) -> usize {
+fn part2(puzzle: &Puzzle, minutes: usize, blueprints: usize) -> usize {
     puzzle
         .blueprints
         .iter()
-        .take(3)
-        .map(|bp| max_geodes(&bp, 32))
-        .fold(1, |a, b| a * b)
+        .take(blueprints)
+        .map(|bp| max_geodes(bp, minutes))
+        .product()
 }
 
+/// Searches the order in which the robots are built, each one as soon as it can be afforded.
 fn max_geodes(blueprint: &Blueprint, minutes: usize) -> usize {
-    let mut simulations = vec![
-        Simulation::new(&blueprint, Res::Ore),
-        Simulation::new(&blueprint, Res::Clay),
-    ];
+    // A single robot is built each minute, so there is no point in collecting more of a resource than can be spent
+    let mut max_robots = [usize::MAX; ROBOT_TYPES];
 
-    let mut new_simulations = vec![];
+    for (resource, max) in max_robots.iter_mut().take(3).enumerate() {
+        *max = blueprint.costs.iter().map(|i| i[resource]).max().unwrap();
+    }
 
-    for minute in 0..minutes {
-        for simulation in simulations.iter_mut() {
-            simulation.collect_resources();
-            simulation.end_building_robots();
+    let mut best = 0;
+    let start = Inventory {
+        robots: [1, 0, 0, 0],
+        resources: [0; ROBOT_TYPES],
+    };
 
-            if simulation.can_build_robot(simulation.next_robot) {
-                simulation.start_building_robot(simulation.next_robot);
-                simulation.branch(&mut new_simulations);
-            }
-        }
+    visit(blueprint, &max_robots, minutes, start, &mut best);
+    best
+}
+
+#[derive(Copy, Clone)]
+struct Inventory {
+    robots: [usize; ROBOT_TYPES],
+    resources: [usize; ROBOT_TYPES],
+}
 
-        simulations.append(&mut new_simulations);
+fn visit(
+    blueprint: &Blueprint,
+    max_robots: &[usize; ROBOT_TYPES],
+    minutes_left: usize,
+    inventory: Inventory,
+    best: &mut usize,
+) {
+    let geodes = inventory.resources[GEODE] + inventory.robots[GEODE] * minutes_left;
+    *best = (*best).max(geodes);
+
+    // Even building a geode robot in each of the remaining minutes would not be enough
+    if geodes + minutes_left * minutes_left.saturating_sub(1) / 2 <= *best {
+        return;
+    }
 
-        let remaining_minutes = minutes - minute - 1;
+    // Trying the geode robots first finds good counts early, which cuts more branches
+    for kind in (0..ROBOT_TYPES).rev() {
+        if inventory.robots[kind] >= max_robots[kind] {
+            continue;
+        }
 
-        let max_possible_score = simulations
-            .iter()
-            .map(|s| s.max_possible_geodes(remaining_minutes))
-            .max()
-            .unwrap();
+        let cost = &blueprint.costs[kind];
 
-        simulations.retain(|s| {
-            // This should have worked without the scaling factor, I don't understand why it doesn't :'(
-            s.max_possible_geodes(remaining_minutes) >= (max_possible_score as f64 * 0.8) as usize
+        let wait = (0..3).try_fold(0, |wait, i| {
+            let missing = cost[i].saturating_sub(inventory.resources[i]);
+            match (missing, inventory.robots[i]) {
+                (0, _) => Some(wait),
+                (_, 0) => None,
+                (missing, robots) => Some(wait.max(missing.div_ceil(robots))),
+            }
         });
-    }
-
-    simulations.iter().map(|s| s.geode_count()).max().unwrap()
-}
 
-impl<'a> Simulation<'a> {
-    fn new(blueprint: &'a Blueprint, next_robot: Res) -> Simulation<'a> {
-        let mut result = Self {
-            blueprint,
-            resources: [ResourceStatus::default(); ROBOT_TYPES],
-            next_robot,
+        // The robot needs to be ready before the last minute to be of any use
+        let Some(wait) = wait.filter(|&wait| wait + 1 < minutes_left) else {
+            continue;
         };
 
-        result.resources[Res::Ore].robots = 1;
-        result
-    }
+        let mut next = inventory;
 
-    fn branch(&mut self, simulations: &mut Vec<Simulation<'a>>) {
-        if !self.has_enough_ore_robots() {
-            let mut clone = self.clone();
-            clone.next_robot = Res::Ore;
-            simulations.push(clone);
+        for i in 0..ROBOT_TYPES {
+            next.resources[i] += inventory.robots[i] * (wait + 1);
         }
 
-        if !self.has_enough_clay_robots() {
-            let mut clone = self.clone();
-            clone.next_robot = Res::Clay;
-            simulations.push(clone);
+        for (resource, cost) in next.resources.iter_mut().zip(cost) {
+            *resource -= cost;
         }
 
-        if !self.has_enough_obsidian_robots() {
-            let mut clone = self.clone();
-            clone.next_robot = Res::Obsidian;
-            simulations.push(clone);
-        }
-
-        self.next_robot = Res::Geode;
-    }
-
-    fn geode_count(&self) -> usize {
-        self.resources[Res::Geode].count
-    }
-
-    fn max_possible_geodes(&self, minutes: usize) -> usize {
-        self.resources[Res::Geode].max_possible_count(minutes)
+        next.robots[kind] += 1;
+        visit(blueprint, max_robots, minutes_left - wait - 1, next, best);
     }
+}
 
-    fn start_building_robot(&mut self, kind: Res) {
-        match kind {
-            Res::Ore => self.start_building_ore_robot(),
-            Res::Clay => self.start_building_clay_robot(),
-            Res::Obsidian => self.start_building_obsidian_robot(),
-            Res::Geode => self.start_building_geode_robot(),
+/// Tries every choice at each minute, which is either waiting or building any robot that can be afforded.
+/// The states are memoized, but no branch is ever cut. With `clamp`, the stocks which can no longer be spent
+/// are capped, so that more states are the same.
+fn reference_max_geodes(blueprint: &Blueprint, minutes: usize, clamp: bool) -> usize {
+    /// The minutes left, and the robots and resources of each type but geodes. The geodes are never spent,
+    /// so the ones collected later do not depend on how many there already are.
+    type State = (u8, [u8; 3], [u16; 3]);
+
+    /// Returns the most geodes which can be collected by the robots built from this state.
+    fn search(
+        costs: &[[usize; 3]; ROBOT_TYPES],
+        state: State,
+        clamp: bool,
+        memo: &mut HashMap<State, usize>,
+    ) -> usize {
+        let (minutes_left, robots, mut resources) = state;
+
+        if minutes_left == 0 {
+            return 0;
         }
-    }
 
-    fn collect_resources(&mut self) {
-        for res in self.resources.iter_mut() {
-            res.collect();
+        // At most one robot is built each minute, so a stock which could not be spent
+        // by the most expensive robot every minute left is as good as that amount
+        if clamp {
+            for (i, resource) in resources.iter_mut().enumerate() {
+                let most = costs.iter().map(|cost| cost[i]).max().unwrap() * minutes_left as usize;
+                *resource = (*resource).min(most as u16);
+            }
         }
-    }
 
-    fn end_building_robots(&mut self) {
-        for res in self.resources.iter_mut() {
-            res.end_building_robots();
-        }
-    }
+        let state = (minutes_left, robots, resources);
 
-    fn can_build_robot(&self, kind: Res) -> bool {
-        match kind {
-            Res::Ore => self.can_build_ore_robot(),
-            Res::Clay => self.can_build_clay_robot(),
-            Res::Obsidian => self.can_build_obsidian_robot(),
-            Res::Geode => self.can_build_geode_robot(),
+        if let Some(&geodes) = memo.get(&state) {
+            return geodes;
         }
-    }
 
-    fn has_enough_ore_robots(&self) -> bool {
-        self.resources[Res::Ore].robots
-            >= self
-                .blueprint
-                .ore_robot_cost_in_ore
-                .max(self.blueprint.clay_robot_cost_in_ore)
-                .max(self.blueprint.obsidian_robot_cost_in_ore)
-                .max(self.blueprint.geode_robot_cost_in_ore)
-    }
+        // The robots collect during the minute, and the one being built is only ready at its end
+        let mut collected = resources;
+        (0..3).for_each(|i| collected[i] += robots[i] as u16);
 
-    fn can_build_ore_robot(&self) -> bool {
-        self.resources[Res::Ore].count >= self.blueprint.ore_robot_cost_in_ore
-    }
+        let mut geodes = search(costs, (minutes_left - 1, robots, collected), clamp, memo);
 
-    fn start_building_ore_robot(&mut self) {
-        self.resources[Res::Ore].count -= self.blueprint.ore_robot_cost_in_ore;
-        self.resources[Res::Ore].pending_robots += 1;
-    }
-
-    fn has_enough_clay_robots(&self) -> bool {
-        self.resources[Res::Clay].robots >= self.blueprint.obsidian_robot_cost_in_clay
-    }
+        for (kind, cost) in costs.iter().enumerate() {
+            if (0..3).any(|i| (resources[i] as usize) < cost[i]) {
+                continue;
+            }
 
-    fn can_build_clay_robot(&self) -> bool {
-        self.resources[Res::Ore].count >= self.blueprint.clay_robot_cost_in_ore
-    }
+            let mut resources = collected;
+            (0..3).for_each(|i| resources[i] -= cost[i] as u16);
+
+            let next = match kind {
+                GEODE => {
+                    // The new geode robot collects one geode in each minute left after this one
+                    let state = (minutes_left - 1, robots, resources);
+                    (minutes_left - 1) as usize + search(costs, state, clamp, memo)
+                }
+                _ => {
+                    let mut robots = robots;
+                    robots[kind] += 1;
+                    search(costs, (minutes_left - 1, robots, resources), clamp, memo)
+                }
+            };
+
+            geodes = geodes.max(next);
+        }
 
-    fn start_building_clay_robot(&mut self) {
-        self.resources[Res::Ore].count -= self.blueprint.clay_robot_cost_in_ore;
-        self.resources[Res::Clay].pending_robots += 1;
+        memo.insert(state, geodes);
+        geodes
     }
 
-    fn has_enough_obsidian_robots(&self) -> bool {
-        self.resources[Res::Obsidian].robots >= self.blueprint.geode_robot_cost_in_obsidian
-    }
+    let minutes = u8::try_from(minutes).expect("Too many minutes for the reference solution");
+    let start = (minutes, [1, 0, 0], [0; 3]);
+    search(&blueprint.costs, start, clamp, &mut HashMap::new())
+}
 
-    fn can_build_obsidian_robot(&self) -> bool {
-        self.resources[Res::Ore].count >= self.blueprint.obsidian_robot_cost_in_ore
-            && self.resources[Res::Clay].count >= self.blueprint.obsidian_robot_cost_in_clay
+impl Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let blueprints = parse_lines(input)?;
+        Ok(Self { blueprints })
     }
+}
 
-    fn start_building_obsidian_robot(&mut self) {
-        self.resources[Res::Ore].count -= self.blueprint.obsidian_robot_cost_in_ore;
-        self.resources[Res::Clay].count -= self.blueprint.obsidian_robot_cost_in_clay;
-        self.resources[Res::Obsidian].pending_robots += 1;
-    }
+impl FromStr for Blueprint {
+    type Err = ParseError;
 
-    fn can_build_geode_robot(&self) -> bool {
-        self.resources[Res::Ore].count >= self.blueprint.geode_robot_cost_in_ore
-            && self.resources[Res::Obsidian].count >= self.blueprint.geode_robot_cost_in_obsidian
-    }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = scan(
+            s,
+            "Blueprint {}: \
+             Each ore robot costs {} ore. \
+             Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. \
+             Each geode robot costs {} ore and {} obsidian.",
+        )?;
+
+        let id = fields.parse()?;
+        let ore_robot = [fields.parse()?, 0, 0];
+        let clay_robot = [fields.parse()?, 0, 0];
+        let obsidian_robot = [fields.parse()?, fields.parse()?, 0];
+        let geode_robot = [fields.parse()?, 0, fields.parse()?];
 
-    fn start_building_geode_robot(&mut self) {
-        self.resources[Res::Ore].count -= self.blueprint.geode_robot_cost_in_ore;
-        self.resources[Res::Obsidian].count -= self.blueprint.geode_robot_cost_in_obsidian;
-        self.resources[Res::Geode].pending_robots += 1;
+        Ok(Self {
+            id,
+            costs: [ore_robot, clay_robot, obsidian_robot, geode_robot],
+        })
     }
 }
 
-impl ResourceStatus {
-    fn collect(&mut self) {
-        self.count += self.robots;
-    }
-
-    fn end_building_robots(&mut self) {
-        self.robots += self.pending_robots;
-        self.pending_robots = 0;
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn max_possible_count(&self, minutes: usize) -> usize {
-        let mut result = self.count;
-        let mut robots = self.robots;
+    const EXAMPLE: &str = "\
+Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
 
-        for _minute in 0..minutes {
-            result += robots;
-            robots += 1;
-        }
+    /// The input generated with seed 4 and size 4.
+    const GENERATED: &str = "\
+Blueprint 1: Each ore robot costs 3 ore. Each clay robot costs 3 ore. Each obsidian robot costs 2 ore and 5 clay. Each geode robot costs 3 ore and 5 obsidian.";
 
-        result
+    #[test]
+    fn part1_example() {
+        let input = Day19::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day19::default().part1(&input), 33);
     }
-}
 
-impl Index<Res> for [ResourceStatus; ROBOT_TYPES] {
-    type Output = ResourceStatus;
-
-    fn index(&self, index: Res) -> &Self::Output {
-        &self[index as usize]
+    #[test]
+    fn part2_example() {
+        let input = Day19::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day19::default().part2(&input), 3472);
     }
-}
 
-impl IndexMut<Res> for [ResourceStatus; ROBOT_TYPES] {
-    fn index_mut(&mut self, index: Res) -> &mut Self::Output {
-        &mut self[index as usize]
+    #[test]
+    fn reference_example() {
+        // The reference is too slow for the full 24 minutes, but the example still opens geodes in 20
+        let solution = Day19 {
+            minutes: 20,
+            long_minutes: 20,
+            ..Default::default()
+        };
+
+        let input = solution.parse(EXAMPLE).unwrap();
+        assert_eq!(
+            solution.reference(&input, Part::One),
+            Some(solution.part1(&input).to_string())
+        );
+        assert_eq!(
+            solution.reference(&input, Part::Two),
+            Some(solution.part2(&input).to_string())
+        );
+        assert_eq!(solution.part1(&input), 6);
+        assert_eq!(solution.part2(&input), 4);
     }
-}
 
-impl Puzzle {
-    fn parse(lines: Vec<String>) -> Puzzle {
-        let mut blueprints = vec![];
+    #[test]
+    fn close_geode_bounds() {
+        // Keeping only the orders whose bound was within 80% of the best bound found 9 geodes here, and not 10
+        let solution = Day19 {
+            long_minutes: 20,
+            ..Default::default()
+        };
 
-        for line in lines {
-            if !line.is_empty() {
-                blueprints.push(Blueprint::from_str(&line).unwrap());
-            }
-        }
+        let input = solution.parse(GENERATED).unwrap();
 
-        Self { blueprints }
+        assert_eq!(solution.part2(&input), 10);
+        assert_eq!(
+            solution.reference(&input, Part::Two),
+            Some("10".to_string())
+        );
     }
-}
-
-impl FromStr for Blueprint {
-    type Err = ();
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"Blueprint (?P<id>[0-9]+): Each ore robot costs (?P<ore_ore>[0-9]+) ore\. Each clay robot costs (?P<clay_ore>[0-9]+) ore\. Each obsidian robot costs (?P<obsidian_ore>[0-9]+) ore and (?P<obsidian_clay>[0-9]+) clay\. Each geode robot costs (?P<geode_ore>[0-9]+) ore and (?P<geode_obsidian>[0-9]+) obsidian\.").unwrap();
-        }
-
-        let cap = RE.captures(s).unwrap();
+    #[test]
+    fn reference_clamp() {
+        // Capping the stocks which can no longer be spent must not change the geodes found
+        let input = Day19::default().parse(GENERATED).unwrap();
+        let blueprint = &input.blueprints[0];
 
-        Ok(Self {
-            id: cap["id"].parse().unwrap(),
-            ore_robot_cost_in_ore: cap["ore_ore"].parse().unwrap(),
-            clay_robot_cost_in_ore: cap["clay_ore"].parse().unwrap(),
-            obsidian_robot_cost_in_ore: cap["obsidian_ore"].parse().unwrap(),
-            obsidian_robot_cost_in_clay: cap["obsidian_clay"].parse().unwrap(),
-            geode_robot_cost_in_ore: cap["geode_ore"].parse().unwrap(),
-            geode_robot_cost_in_obsidian: cap["geode_obsidian"].parse().unwrap(),
-        })
+        assert_eq!(reference_max_geodes(blueprint, 18, true), 5);
+        assert_eq!(reference_max_geodes(blueprint, 18, false), 5);
     }
 }
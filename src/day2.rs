@@ -1,11 +1,26 @@
-use crate::common::get_input_lines;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
+use std::str::FromStr;
 
+#[derive(Copy, Clone)]
 enum Shape {
     Rock,
     Paper,
     Scissors,
 }
 
+#[derive(Copy, Clone)]
+enum Column {
+    X,
+    Y,
+    Z,
+}
+
+pub struct Entry {
+    opponent: Shape,
+    column: Column,
+}
+
 struct Round {
     pub opponent: Shape,
     pub player: Shape,
@@ -17,67 +32,79 @@ enum Outcome {
     Loss,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let input = get_input_lines();
+pub struct Day2;
 
-    let score = input
-        .iter()
-        .filter_map(|i| parse_round_part1(&i))
-        .map(|i| outcome_score(play(&i)) + shape_score(i.player))
-        .sum::<i32>();
+impl Solution for Day2 {
+    type Input = Vec<Entry>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-    println!("Score (part 1): {}", score);
+    fn parse(&self, input: &str) -> Result<Vec<Entry>, ParseError> {
+        parse_lines(input)
+    }
 
-    let score = input
-        .iter()
-        .filter_map(|i| parse_round_part2(&i))
-        .map(|i| outcome_score(play(&i)) + shape_score(i.player))
-        .sum::<i32>();
+    fn part1(&self, input: &Vec<Entry>) -> i32 {
+        input
+            .iter()
+            .map(round_part1)
+            .map(|i| outcome_score(play(&i)) + shape_score(i.player))
+            .sum::<i32>()
+    }
 
-    println!("Score (part 2): {}", score);
+    fn part2(&self, input: &Vec<Entry>) -> i32 {
+        input
+            .iter()
+            .map(round_part2)
+            .map(|i| outcome_score(play(&i)) + shape_score(i.player))
+            .sum::<i32>()
+    }
 }
 
-fn parse_round_part1(line: &str) -> Option<Round> {
-    let line = line.split_whitespace().collect::<Vec<_>>();
-    if line.len() != 2 {
-        return None;
-    }
+impl FromStr for Entry {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        if tokens.len() != 2 {
+            return Err(ParseError::new("Expected two letters"));
+        }
 
-    Some(Round {
-        opponent: match line[0] {
+        let opponent = match tokens[0] {
             "A" => Shape::Rock,
             "B" => Shape::Paper,
             "C" => Shape::Scissors,
-            _ => panic!(),
-        },
-        player: match line[1] {
-            "X" => Shape::Rock,
-            "Y" => Shape::Paper,
-            "Z" => Shape::Scissors,
-            _ => panic!(),
-        },
-    })
+            token => return Err(ParseError::at(line, token, "Expected A, B or C")),
+        };
+
+        let column = match tokens[1] {
+            "X" => Column::X,
+            "Y" => Column::Y,
+            "Z" => Column::Z,
+            token => return Err(ParseError::at(line, token, "Expected X, Y or Z")),
+        };
+
+        Ok(Entry { opponent, column })
+    }
 }
 
-fn parse_round_part2(line: &str) -> Option<Round> {
-    let line = line.split_whitespace().collect::<Vec<_>>();
-    if line.len() != 2 {
-        return None;
+fn round_part1(entry: &Entry) -> Round {
+    Round {
+        opponent: entry.opponent,
+        player: match entry.column {
+            Column::X => Shape::Rock,
+            Column::Y => Shape::Paper,
+            Column::Z => Shape::Scissors,
+        },
     }
+}
 
-    let opponent = match line[0] {
-        "A" => Shape::Rock,
-        "B" => Shape::Paper,
-        "C" => Shape::Scissors,
-        _ => panic!(),
-    };
+fn round_part2(entry: &Entry) -> Round {
+    let opponent = entry.opponent;
 
-    let outcome = match line[1] {
-        "X" => Outcome::Loss,
-        "Y" => Outcome::Draw,
-        "Z" => Outcome::Win,
-        _ => panic!(),
+    let outcome = match entry.column {
+        Column::X => Outcome::Loss,
+        Column::Y => Outcome::Draw,
+        Column::Z => Outcome::Win,
     };
 
     let player = match (&opponent, &outcome) {
@@ -92,7 +119,7 @@ fn parse_round_part2(line: &str) -> Option<Round> {
         (Shape::Scissors, Outcome::Loss) => Shape::Paper,
     };
 
-    Some(Round { opponent, player })
+    Round { opponent, player }
 }
 
 fn play(round: &Round) -> Outcome {
@@ -124,3 +151,25 @@ fn outcome_score(outcome: Outcome) -> i32 {
         Outcome::Loss => 0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+A Y
+B X
+C Z";
+
+    #[test]
+    fn part1_example() {
+        let input = Day2.parse(EXAMPLE).unwrap();
+        assert_eq!(Day2.part1(&input), 15);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day2.parse(EXAMPLE).unwrap();
+        assert_eq!(Day2.part2(&input), 12);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::common::get_input_lines;
+use crate::error::{parse_token, ParseError};
+use crate::params;
+use crate::solution::Solution;
 
 #[derive(Copy, Clone)]
 struct Number {
@@ -7,19 +9,44 @@ struct Number {
 }
 
 #[derive(Clone)]
-struct File {
+pub struct File {
     numbers: Vec<Number>,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let file = File::parse(get_input_lines());
+pub struct Day20 {
+    /// The factor applied to the numbers in part 2.
+    pub decryption_key: isize,
+    /// The number of times the file is mixed in part 2.
+    pub mix_rounds: usize,
+}
+
+impl Default for Day20 {
+    fn default() -> Self {
+        Day20 {
+            decryption_key: 811_589_153,
+            mix_rounds: 10,
+        }
+    }
+}
+
+impl Solution for Day20 {
+    type Input = File;
+    type Answer1 = isize;
+    type Answer2 = isize;
 
-    let result = part1(file.clone());
-    println!("Result (part 1): {result}");
+    fn parse(&self, input: &str) -> Result<File, ParseError> {
+        File::parse(input)
+    }
+
+    fn part1(&self, file: &File) -> isize {
+        part1(file.clone())
+    }
 
-    let result = part2(file);
-    println!("Result (part 2): {result}");
+    fn part2(&self, file: &File) -> isize {
+        part2(file.clone(), self.decryption_key, self.mix_rounds)
+    }
+
+    params!(decryption_key, mix_rounds);
 }
 
 fn part1(mut file: File) -> isize {
@@ -27,12 +54,12 @@ fn part1(mut file: File) -> isize {
     file.get_result()
 }
 
-fn part2(mut file: File) -> isize {
-    for mut n in file.numbers.iter_mut() {
-        n.value *= 811589153;
+fn part2(mut file: File, decryption_key: isize, mix_rounds: usize) -> isize {
+    for n in file.numbers.iter_mut() {
+        n.value *= decryption_key;
     }
 
-    for _ in 0..10 {
+    for _ in 0..mix_rounds {
         file.mix()
     }
 
@@ -40,23 +67,36 @@ fn part2(mut file: File) -> isize {
 }
 
 impl File {
-    fn parse(lines: Vec<String>) -> File {
-        let numbers = lines
-            .iter()
-            .filter_map(|i| i.parse().ok())
+    fn parse(input: &str) -> Result<File, ParseError> {
+        let numbers = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(index, line)| parse_token(line, line.trim()).map_err(|e| e.at_line(index + 1)))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .enumerate()
             .map(|(i, n)| Number {
                 value: n,
                 orig_index: i,
             })
-            .collect();
+            .collect::<Vec<_>>();
 
-        File { numbers }
+        if !numbers.iter().any(|i| i.value == 0) {
+            return Err(ParseError::new("The file must contain a 0"));
+        }
+
+        Ok(File { numbers })
     }
 
     fn mix(&mut self) {
         let numbers = &mut self.numbers;
 
+        // A single number has nowhere to move
+        if numbers.len() < 2 {
+            return;
+        }
+
         for iter in 0..numbers.len() {
             let index = numbers.iter().position(|i| i.orig_index == iter).unwrap();
             let number = numbers[index];
@@ -84,3 +124,36 @@ impl File {
         self.numbers[index % self.numbers.len()].value
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1
+2
+-3
+3
+-2
+0
+4";
+
+    #[test]
+    fn part1_example() {
+        let input = Day20::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day20::default().part1(&input), 3);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day20::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day20::default().part2(&input), 1623178306);
+    }
+
+    #[test]
+    fn single_number() {
+        let input = Day20::default().parse("0").unwrap();
+        assert_eq!(Day20::default().part1(&input), 0);
+        assert_eq!(Day20::default().part2(&input), 0);
+    }
+}
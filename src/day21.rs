@@ -1,6 +1,6 @@
-use crate::common::get_input_lines;
-use lazy_static::lazy_static;
-use regex::Regex;
+use crate::error::{parse_lines, ParseError};
+use crate::parse::scan;
+use crate::solution::Solution;
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::iter;
@@ -29,11 +29,11 @@ enum MonkeyJob {
 }
 
 #[derive(Clone)]
-struct Puzzle {
+pub struct Puzzle {
     monkeys: HashMap<String, Monkey>,
 }
 
-struct DAG<'a, T>
+struct Dag<'a, T>
 where
     T: Node,
 {
@@ -47,19 +47,28 @@ trait Node {
     fn get_edges(&self) -> Vec<&Self::Key>;
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let puzzle = Puzzle::parse(get_input_lines());
+pub struct Day21;
 
-    let result = part1(puzzle.clone());
-    println!("Result (part 1): {result}");
+impl Solution for Day21 {
+    type Input = Puzzle;
+    type Answer1 = isize;
+    type Answer2 = isize;
 
-    let result = part2(puzzle);
-    println!("Result (part 2): {result}");
+    fn parse(&self, input: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::parse(input)
+    }
+
+    fn part1(&self, puzzle: &Puzzle) -> isize {
+        part1(puzzle.clone())
+    }
+
+    fn part2(&self, puzzle: &Puzzle) -> isize {
+        part2(puzzle.clone())
+    }
 }
 
 fn part1(mut puzzle: Puzzle) -> isize {
-    let dag = DAG::new(puzzle.monkeys.values(), iter::once(String::from("root")));
+    let dag = Dag::new(puzzle.monkeys.values(), iter::once(String::from("root")));
     let sorted = dag
         .topo_sort()
         .iter()
@@ -75,10 +84,10 @@ fn part1(mut puzzle: Puzzle) -> isize {
 }
 
 fn part2(mut puzzle: Puzzle) -> isize {
-    let mut human = puzzle.get_node_mut("humn");
+    let human = puzzle.get_node_mut("humn");
     human.job = MonkeyJob::Dunno;
 
-    let dag = DAG::new(puzzle.monkeys.values(), iter::once(String::from("root")));
+    let dag = Dag::new(puzzle.monkeys.values(), iter::once(String::from("root")));
     let sorted = dag
         .topo_sort()
         .iter()
@@ -117,14 +126,14 @@ fn part2(mut puzzle: Puzzle) -> isize {
                 let b = puzzle.get_node(b);
 
                 match (a.value, op, b.value) {
-                    (None, Op::Add, Some(b)) => solve(&puzzle, &a.name, target - b),
-                    (Some(a), Op::Add, None) => solve(&puzzle, &b.name, target - a),
-                    (None, Op::Sub, Some(b)) => solve(&puzzle, &a.name, target + b),
-                    (Some(a), Op::Sub, None) => solve(&puzzle, &b.name, a - target),
-                    (None, Op::Mul, Some(b)) => solve(&puzzle, &a.name, target / b),
-                    (Some(a), Op::Mul, None) => solve(&puzzle, &b.name, target / a),
-                    (None, Op::Div, Some(b)) => solve(&puzzle, &a.name, target * b),
-                    (Some(a), Op::Div, None) => solve(&puzzle, &b.name, a / target),
+                    (None, Op::Add, Some(b)) => solve(puzzle, &a.name, target - b),
+                    (Some(a), Op::Add, None) => solve(puzzle, &b.name, target - a),
+                    (None, Op::Sub, Some(b)) => solve(puzzle, &a.name, target + b),
+                    (Some(a), Op::Sub, None) => solve(puzzle, &b.name, a - target),
+                    (None, Op::Mul, Some(b)) => solve(puzzle, &a.name, target / b),
+                    (Some(a), Op::Mul, None) => solve(puzzle, &b.name, target / a),
+                    (None, Op::Div, Some(b)) => solve(puzzle, &a.name, target * b),
+                    (Some(a), Op::Div, None) => solve(puzzle, &b.name, a / target),
                     _ => panic!(),
                 }
             }
@@ -136,16 +145,38 @@ fn part2(mut puzzle: Puzzle) -> isize {
 }
 
 impl Puzzle {
-    fn parse(lines: Vec<String>) -> Puzzle {
-        Self {
-            monkeys: lines
-                .iter()
-                .map(|i| {
-                    let monkey = i.parse::<Monkey>().unwrap();
-                    (monkey.name.clone(), monkey)
-                })
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let puzzle = Self {
+            monkeys: parse_lines::<Monkey>(input)?
+                .into_iter()
+                .map(|monkey| (monkey.name.clone(), monkey))
                 .collect(),
+        };
+
+        for name in ["root", "humn"] {
+            if !puzzle.monkeys.contains_key(name) {
+                return Err(ParseError::new(format!("Missing monkey: {name}")));
+            }
+        }
+
+        if !matches!(puzzle.get_node("root").job, MonkeyJob::Formula(..)) {
+            return Err(ParseError::new("The root monkey must yell an operation"));
         }
+
+        for monkey in puzzle.monkeys.values() {
+            if let MonkeyJob::Formula(a, _, b) = &monkey.job {
+                for name in [a, b] {
+                    if !puzzle.monkeys.contains_key(name) {
+                        return Err(ParseError::new(format!(
+                            "Monkey {} refers to unknown monkey {name}",
+                            monkey.name
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(puzzle)
     }
 
     fn process(&mut self, name: &str) {
@@ -182,7 +213,7 @@ impl Puzzle {
     }
 }
 
-impl<'a, T> DAG<'a, T>
+impl<'a, T> Dag<'a, T>
 where
     T: Node,
 {
@@ -205,7 +236,7 @@ where
         let mut statuses = HashMap::new();
 
         while let Some((key, enter)) = stack.pop() {
-            let node = self.get_node(&key).unwrap();
+            let node = self.get_node(key).unwrap();
             if enter {
                 match statuses.get_mut(&key) {
                     Some(Status::Visited) => continue,
@@ -214,10 +245,10 @@ where
                 };
 
                 statuses.insert(key, Status::Visiting);
-                stack.push((&key, false));
+                stack.push((key, false));
 
                 for edge in node.get_edges() {
-                    stack.push((&edge, true));
+                    stack.push((edge, true));
                 }
             } else {
                 statuses.insert(key, Status::Visited);
@@ -250,36 +281,36 @@ impl Node for Monkey {
 }
 
 impl FromStr for Monkey {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(
-                r"^\s*(?P<name>\w+)\s*:\s*(?:(?P<n>\d+)|(?P<a>\w+)\s*(?P<op>[-+*/])\s*(?P<b>\w+))\s*$"
-            )
-            .unwrap();
-        }
+        let s = s.trim();
 
-        let cap = RE.captures(s).unwrap();
+        let (name, job) = match scan(s, "{}: {} {} {}") {
+            Ok(mut fields) => (
+                fields.text(),
+                MonkeyJob::Formula(
+                    fields.text().to_string(),
+                    fields.parse()?,
+                    fields.text().to_string(),
+                ),
+            ),
+            Err(_) => {
+                let mut fields = scan(s, "{}: {}")?;
+                (fields.text(), MonkeyJob::Value(fields.parse()?))
+            }
+        };
 
         Ok(Self {
-            name: cap.name("name").unwrap().as_str().to_string(),
+            name: name.to_string(),
             value: None,
-            job: if let Some(n) = cap.name("n") {
-                MonkeyJob::Value(n.as_str().parse().unwrap())
-            } else {
-                MonkeyJob::Formula(
-                    cap.name("a").unwrap().as_str().to_string(),
-                    cap.name("op").unwrap().as_str().parse().unwrap(),
-                    cap.name("b").unwrap().as_str().to_string(),
-                )
-            },
+            job,
         })
     }
 }
 
 impl FromStr for Op {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
@@ -287,7 +318,41 @@ impl FromStr for Op {
             "-" => Op::Sub,
             "*" => Op::Mul,
             "/" => Op::Div,
-            _ => panic!(),
+            _ => return Err(ParseError::new(format!("Invalid operator: '{s}'"))),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    #[test]
+    fn part1_example() {
+        let input = Day21.parse(EXAMPLE).unwrap();
+        assert_eq!(Day21.part1(&input), 152);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day21.parse(EXAMPLE).unwrap();
+        assert_eq!(Day21.part2(&input), 301);
+    }
+}
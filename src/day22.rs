@@ -1,4 +1,11 @@
-use crate::common::get_input_lines;
+use crate::common::Part;
+use crate::error::{parse_token, ParseError};
+use crate::geometry::{Direction, Point2, Point3, Turn};
+use crate::grid::Grid;
+use crate::render::{Canvas, Color, Frames};
+use crate::solution::Solution;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
 
 #[derive(Copy, Clone, PartialEq)]
 enum Tile {
@@ -10,41 +17,90 @@ enum Tile {
 #[derive(Copy, Clone)]
 enum Step {
     Advance(usize),
-    Rotate(Rotation),
+    Rotate(Turn),
 }
 
-#[derive(Copy, Clone)]
-enum Rotation {
-    Left,
-    Right,
-}
-
-#[derive(Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Copy, Clone)]
-struct Coord(isize, isize);
+type Coord = Point2<isize>;
+type Vector = Point3<i32>;
 
 #[derive(Clone)]
-struct Puzzle {
-    board: Vec<Vec<Tile>>,
+pub struct Puzzle {
+    board: Grid<Tile>,
     steps: Vec<Step>,
     coord: Coord,
     direction: Direction,
-    width: usize,
+    /// The cube the board folds into, found when parsing it, or `None` when it is not the net of a cube.
+    cube: Option<Cube>,
+    /// Whether the moves wrap around the cube rather than around the board.
+    folded: bool,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let puzzle = Puzzle::parse(get_input_lines());
+/// The board folded into a cube, where each face is a square of the board.
+#[derive(Clone)]
+struct Cube {
+    /// The width of the faces, in tiles.
+    size: isize,
+    /// The index of the face at each square of the board, where a square is as wide as a face.
+    layout: Grid<Option<usize>>,
+    /// The position of each face, in squares.
+    faces: Vec<Coord>,
+    /// Where leaving each face leads, for each direction of [`Direction::ALL`].
+    edges: Vec<[Edge; 4]>,
+}
+
+/// Where leaving a face through one of its edges leads.
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+    face: usize,
+    /// The direction followed on the next face.
+    direction: Direction,
+    /// Whether the position along the edge goes the other way on the next face.
+    reversed: bool,
+}
 
-    let result = part1(puzzle.clone());
-    println!("Result (part 1): {result}");
+/// A face being folded, along with its place on the cube, as vectors pointing out of the cube
+/// and along the `x` and `y` axes of the board.
+#[derive(Copy, Clone)]
+struct Fold {
+    square: Coord,
+    normal: Vector,
+    right: Vector,
+    down: Vector,
+}
+
+/// The error of part 2 when the board is not the net of a cube.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct NotACube;
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Puzzle;
+    type Answer1 = usize;
+    type Answer2 = Result<usize, NotACube>;
+
+    fn parse(&self, input: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::parse(input)
+    }
+
+    fn part1(&self, puzzle: &Puzzle) -> usize {
+        part1(puzzle.clone())
+    }
+
+    fn part2(&self, puzzle: &Puzzle) -> Result<usize, NotACube> {
+        part2(puzzle.clone())
+    }
+
+    fn render(&self, puzzle: &Puzzle, part: Part, frames: &mut Frames) -> bool {
+        let mut puzzle = puzzle.clone();
+
+        if part == Part::Two && !puzzle.fold() {
+            return false;
+        }
+
+        render(puzzle, frames);
+        true
+    }
 }
 
 fn part1(mut puzzle: Puzzle) -> usize {
@@ -52,55 +108,106 @@ fn part1(mut puzzle: Puzzle) -> usize {
     puzzle.get_password()
 }
 
-impl Puzzle {
-    fn parse(lines: Vec<String>) -> Puzzle {
-        let mut board = vec![];
+fn part2(mut puzzle: Puzzle) -> Result<usize, NotACube> {
+    if !puzzle.fold() {
+        return Err(NotACube);
+    }
 
-        let mut lines_iter = lines.iter();
-        while let Some(str) = lines_iter.next() {
-            if str.is_empty() {
-                break;
-            }
+    puzzle.process();
+    Ok(puzzle.get_password())
+}
 
-            let line = str
-                .chars()
-                .map(|c| match c {
-                    ' ' => Tile::None,
-                    '.' => Tile::Open,
-                    '#' => Tile::Wall,
-                    _ => panic!(),
-                })
-                .collect::<Vec<_>>();
+/// Follows the path one tile at a time, drawing the board along with the trail after each move.
+fn render(mut puzzle: Puzzle, frames: &mut Frames) {
+    let mut trail = Grid::new(puzzle.board_width(), puzzle.board_height(), false);
+    let mut visit = |puzzle: &Puzzle, frames: &mut Frames| {
+        trail[puzzle.coord] = true;
+        frames.push(|| puzzle.draw(&trail));
+    };
 
-            board.push(line);
-        }
+    visit(&puzzle, frames);
 
-        let steps_str = lines_iter.next().unwrap();
-        let mut steps = vec![];
-        for value in steps_str.split_inclusive(&['R', 'L']) {
-            let last_char = value.chars().last().unwrap();
-            if last_char.is_numeric() {
-                steps.push(Step::Advance(value.parse().unwrap()));
-            } else {
-                steps.push(Step::Advance(value[0..value.len() - 1].parse().unwrap()));
-                steps.push(Step::Rotate(match last_char {
-                    'R' => Rotation::Right,
-                    'L' => Rotation::Left,
-                    _ => panic!(),
-                }));
+    for i in 0..puzzle.steps.len() {
+        match puzzle.steps[i] {
+            Step::Advance(n) => {
+                for _ in 0..n {
+                    let previous = puzzle.coord;
+                    puzzle.advance(1);
+
+                    if puzzle.coord == previous {
+                        break;
+                    }
+
+                    visit(&puzzle, frames);
+                }
             }
+            Step::Rotate(turn) => puzzle.rotate(turn),
         }
+    }
 
-        let x = board[0].iter().position(|&i| i == Tile::Open).unwrap();
-        let width = board.iter().map(|i| i.len()).max().unwrap();
+    frames.finish(|| puzzle.draw(&trail));
+}
 
-        Puzzle {
-            board,
+impl Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let board_rows = input.lines().take_while(|i| !i.is_empty()).count();
+        let board = input
+            .lines()
+            .take(board_rows)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let board = Grid::parse_ragged(&board, Tile::None, |c| match c {
+            ' ' => Ok(Tile::None),
+            '.' => Ok(Tile::Open),
+            '#' => Ok(Tile::Wall),
+            _ => Err(ParseError::new(format!("Invalid tile: '{c}'"))),
+        })?;
+
+        let (index, steps_str) = input
+            .lines()
+            .enumerate()
+            .nth(board_rows + 1)
+            .ok_or_else(|| ParseError::new("Missing path description after the board"))?;
+
+        let steps = Self::parse_steps(steps_str).map_err(|e| e.at_line(index + 1))?;
+
+        let x = board
+            .rows()
+            .next()
+            .and_then(|i| i.iter().position(|&i| i == Tile::Open))
+            .ok_or_else(|| ParseError::new("No open tile on the first row").at_line(1))?;
+
+        Ok(Puzzle {
             steps,
-            coord: Coord(x as isize, 0),
+            coord: Coord::new(x as isize, 0),
             direction: Direction::Right,
-            width,
+            cube: Cube::fold(&board),
+            board,
+            folded: false,
+        })
+    }
+
+    fn parse_steps(line: &str) -> Result<Vec<Step>, ParseError> {
+        let mut steps = vec![];
+
+        for value in line.split_inclusive(&['R', 'L']) {
+            let (number, rotation) = match value.char_indices().last() {
+                Some((i, 'R')) => (&value[..i], Some(Turn::Right)),
+                Some((i, 'L')) => (&value[..i], Some(Turn::Left)),
+                _ => (value, None),
+            };
+
+            if !number.is_empty() {
+                steps.push(Step::Advance(parse_token(line, number)?));
+            }
+
+            if let Some(rotation) = rotation {
+                steps.push(Step::Rotate(rotation));
+            }
         }
+
+        Ok(steps)
     }
 
     fn process(&mut self) {
@@ -116,27 +223,30 @@ impl Puzzle {
         }
     }
 
-    fn rotate(&mut self, rotation: Rotation) {
-        self.direction = match (self.direction, rotation) {
-            (Direction::Up, Rotation::Right) => Direction::Right,
-            (Direction::Right, Rotation::Right) => Direction::Down,
-            (Direction::Down, Rotation::Right) => Direction::Left,
-            (Direction::Left, Rotation::Right) => Direction::Up,
-            (Direction::Up, Rotation::Left) => Direction::Left,
-            (Direction::Left, Rotation::Left) => Direction::Down,
-            (Direction::Down, Rotation::Left) => Direction::Right,
-            (Direction::Right, Rotation::Left) => Direction::Up,
-        }
+    fn rotate(&mut self, turn: Turn) {
+        self.direction = self.direction.turn(turn);
+    }
+
+    /// Makes the moves wrap around the cube the board folds into, instead of around the board.
+    /// Returns `false`, leaving the moves unchanged, when the board is not the net of a cube.
+    fn fold(&mut self) -> bool {
+        self.folded = self.cube.is_some();
+        self.folded
     }
 
     fn advance(&mut self, steps: usize) {
         for _ in 0..steps {
-            let next_coord = self.get_coord_ahead();
-            let tile_ahead = self.get_tile(next_coord);
-            match tile_ahead {
-                Tile::None => panic!(),
+            let (next_coord, next_direction) = match &self.cube {
+                Some(cube) if self.folded => cube.ahead(self.coord, self.direction),
+                _ => (self.get_coord_ahead(), self.direction),
+            };
+
+            match self.get_tile(next_coord) {
+                // Wrapping around the board skips the empty tiles, and the cube is made of the faces of the board
+                Tile::None => unreachable!("Moved off the board from {:?}", self.coord),
                 Tile::Open => {
                     self.coord = next_coord;
+                    self.direction = next_direction;
                 }
                 Tile::Wall => break,
             }
@@ -144,35 +254,29 @@ impl Puzzle {
     }
 
     fn get_tile(&self, coord: Coord) -> Tile {
-        if coord.0 < 0 || coord.1 < 0 || coord.1 as usize >= self.board.len() {
-            return Tile::None;
-        }
-
-        let line = &self.board[coord.1 as usize];
-        line.get(coord.0 as usize).copied().unwrap_or(Tile::None)
+        self.board.get_at(coord).copied().unwrap_or(Tile::None)
     }
 
     fn get_coord_ahead(&self) -> Coord {
-        let next = self.coord.next(self.direction);
+        let next = self.coord.step(self.direction);
 
         match self.get_tile(next) {
-            Tile::Open => next,
-            Tile::Wall => self.coord,
+            Tile::Open | Tile::Wall => next,
             Tile::None => match self.direction {
                 Direction::Up => (0..self.board_height())
-                    .map(|y| Coord(self.coord.0, y as isize))
+                    .map(|y| Coord::new(self.coord.x, y as isize))
                     .rfind(|&i| self.get_tile(i) != Tile::None)
                     .unwrap(),
                 Direction::Down => (0..self.board_height())
-                    .map(|y| Coord(self.coord.0, y as isize))
+                    .map(|y| Coord::new(self.coord.x, y as isize))
                     .find(|&i| self.get_tile(i) != Tile::None)
                     .unwrap(),
                 Direction::Left => (0..self.board_width())
-                    .map(|x| Coord(x as isize, self.coord.1))
+                    .map(|x| Coord::new(x as isize, self.coord.y))
                     .rfind(|&i| self.get_tile(i) != Tile::None)
                     .unwrap(),
                 Direction::Right => (0..self.board_width())
-                    .map(|x| Coord(x as isize, self.coord.1))
+                    .map(|x| Coord::new(x as isize, self.coord.y))
                     .find(|&i| self.get_tile(i) != Tile::None)
                     .unwrap(),
             },
@@ -180,8 +284,8 @@ impl Puzzle {
     }
 
     fn get_password(&self) -> usize {
-        ((self.coord.1 as usize + 1) * 1000)
-            + ((self.coord.0 as usize + 1) * 4)
+        ((self.coord.y as usize + 1) * 1000)
+            + ((self.coord.x as usize + 1) * 4)
             + match self.direction {
                 Direction::Up => 3,
                 Direction::Down => 1,
@@ -190,22 +294,368 @@ impl Puzzle {
             }
     }
 
+    fn draw(&self, trail: &Grid<bool>) -> Canvas {
+        let mut canvas = Canvas::from_grid(&self.board, Color::BLACK, |tile| match tile {
+            Tile::None => Color::BLACK,
+            Tile::Open => Color::WHITE,
+            Tile::Wall => Color::GRAY,
+        });
+
+        for ((x, y), _) in trail.iter().filter(|(_, &visited)| visited) {
+            canvas.set(x, y, Color::BLUE);
+        }
+
+        canvas.set(self.coord.x, self.coord.y, Color::RED);
+        canvas.set_focus(self.coord.x as usize, self.coord.y as usize);
+        canvas
+    }
+
     fn board_width(&self) -> usize {
-        self.width
+        self.board.width()
     }
 
     fn board_height(&self) -> usize {
-        self.board.len()
+        self.board.height()
+    }
+}
+
+impl Cube {
+    /// Finds the size of the faces from the area of the board, then folds the faces one after the other
+    /// from the first one. Returns `None` when the board is not the net of a cube.
+    fn fold(board: &Grid<Tile>) -> Option<Cube> {
+        let tiles = board.iter().filter(|(_, &tile)| tile != Tile::None).count();
+        let size = (1..).find(|i| i * i * 6 >= tiles)?;
+
+        if size * size * 6 != tiles {
+            return None;
+        }
+
+        let is_face = |square: Coord| {
+            (0..size as isize).all(|dy| {
+                (0..size as isize).all(|dx| {
+                    let coord = square * size as isize + Coord::new(dx, dy);
+                    board.get_at(coord).is_some_and(|&tile| tile != Tile::None)
+                })
+            })
+        };
+
+        let width = board.width().div_ceil(size);
+        let height = board.height().div_ceil(size);
+
+        let mut layout = Grid::new(width, height, None);
+        let first = (0..width as isize)
+            .map(|x| Coord::new(x, 0))
+            .find(|&i| is_face(i))?;
+
+        layout[first] = Some(0);
+
+        let mut folds = vec![Fold {
+            square: first,
+            normal: Vector::new(0, 0, 1),
+            right: Vector::new(1, 0, 0),
+            down: Vector::new(0, 1, 0),
+        }];
+
+        let mut index = 0;
+
+        while let Some(&fold) = folds.get(index) {
+            for direction in Direction::ALL {
+                let square = fold.square.step(direction);
+
+                if layout.get_at(square) == Some(&None) && is_face(square) {
+                    layout[square] = Some(folds.len());
+                    folds.push(fold.next(direction, square));
+                }
+            }
+
+            index += 1;
+        }
+
+        // The six squares fold into a cube when they end up on different sides
+        let sides = folds.iter().map(|i| i.normal).collect::<HashSet<_>>();
+
+        if folds.len() != 6 || sides.len() != 6 {
+            return None;
+        }
+
+        let edges = folds
+            .iter()
+            .map(|fold| Direction::ALL.map(|direction| fold.edge(direction, &folds)))
+            .collect();
+
+        Some(Cube {
+            size: size as isize,
+            layout,
+            faces: folds.iter().map(|i| i.square).collect(),
+            edges,
+        })
+    }
+
+    /// Returns the tile ahead along with the direction to follow from there,
+    /// which changes when the move continues on the next face of the cube.
+    fn ahead(&self, coord: Coord, direction: Direction) -> (Coord, Direction) {
+        let n = self.size;
+        let next = coord.step(direction);
+        let face = self.face_at(coord).expect("Not on the cube");
+
+        if self.face_at(next) == Some(face) {
+            return (next, direction);
+        }
+
+        let edge = self.edges[face][side(direction)];
+
+        let offset = match direction {
+            Direction::Left | Direction::Right => coord.y.rem_euclid(n),
+            Direction::Up | Direction::Down => coord.x.rem_euclid(n),
+        };
+
+        let offset = if edge.reversed {
+            n - 1 - offset
+        } else {
+            offset
+        };
+
+        let entry = match edge.direction {
+            Direction::Right => Coord::new(0, offset),
+            Direction::Left => Coord::new(n - 1, offset),
+            Direction::Down => Coord::new(offset, 0),
+            Direction::Up => Coord::new(offset, n - 1),
+        };
+
+        (self.faces[edge.face] * n + entry, edge.direction)
+    }
+
+    fn face_at(&self, coord: Coord) -> Option<usize> {
+        let (x, y) = (coord.x.div_euclid(self.size), coord.y.div_euclid(self.size));
+        self.layout.get(x, y).copied().flatten()
     }
 }
 
-impl Coord {
-    fn next(&self, dir: Direction) -> Coord {
-        match dir {
-            Direction::Up => Coord(self.0, self.1 - 1),
-            Direction::Down => Coord(self.0, self.1 + 1),
-            Direction::Left => Coord(self.0 - 1, self.1),
-            Direction::Right => Coord(self.0 + 1, self.1),
+impl Fold {
+    /// Returns the square next to this face in the given direction on the board, folded along their common edge.
+    fn next(&self, direction: Direction, square: Coord) -> Fold {
+        let (normal, right, down) = match direction {
+            Direction::Right => (self.right, -self.normal, self.down),
+            Direction::Left => (-self.right, self.normal, self.down),
+            Direction::Down => (self.down, self.right, -self.normal),
+            Direction::Up => (-self.down, self.right, self.normal),
+        };
+
+        Fold {
+            square,
+            normal,
+            right,
+            down,
+        }
+    }
+
+    /// Finds where leaving this face in the given direction leads, among all the folded faces.
+    fn edge(&self, direction: Direction, folds: &[Fold]) -> Edge {
+        let face = folds
+            .iter()
+            .position(|i| i.normal == self.axis(direction))
+            .unwrap();
+
+        let next = &folds[face];
+        let next_direction = next.direction_of(-self.normal);
+
+        // The position along the edge is measured in the same 3D direction on both faces, unless reversed
+        let along = |fold: &Fold, direction| match direction {
+            Direction::Left | Direction::Right => fold.down,
+            Direction::Up | Direction::Down => fold.right,
+        };
+
+        Edge {
+            face,
+            direction: next_direction,
+            reversed: along(self, direction) != along(next, next_direction),
+        }
+    }
+
+    /// Returns the 3D direction of a move on this face.
+    fn axis(&self, direction: Direction) -> Vector {
+        match direction {
+            Direction::Right => self.right,
+            Direction::Left => -self.right,
+            Direction::Down => self.down,
+            Direction::Up => -self.down,
+        }
+    }
+
+    fn direction_of(&self, axis: Vector) -> Direction {
+        Direction::ALL
+            .into_iter()
+            .find(|&i| self.axis(i) == axis)
+            .expect("The axis is not on the face")
+    }
+}
+
+impl Display for NotACube {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The board does not fold into a cube")
+    }
+}
+
+/// Returns the index of a direction in [`Direction::ALL`].
+fn side(direction: Direction) -> usize {
+    Direction::ALL.iter().position(|&i| i == direction).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::CUBE_NETS;
+
+    const EXAMPLE: &str = r"        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+    #[test]
+    fn part1_example() {
+        let input = Day22.parse(EXAMPLE).unwrap();
+        assert_eq!(Day22.part1(&input), 6032);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day22.parse(EXAMPLE).unwrap();
+        assert_eq!(Day22.part2(&input), Ok(5031));
+    }
+
+    /// Returns an open board made of the faces marked with `#` in `layout`.
+    fn open_board(layout: &str, size: usize) -> Grid<Tile> {
+        let layout = Grid::parse_ragged(layout, false, |c| Ok(c == '#')).unwrap();
+
+        Grid::from_fn(
+            layout.width() * size,
+            layout.height() * size,
+            |x, y| match layout[(x / size, y / size)] {
+                true => Tile::Open,
+                false => Tile::None,
+            },
+        )
+    }
+
+    /// Checks that going straight from any tile of a cube leads back to it after going around the cube.
+    fn assert_loops_around(board: &Grid<Tile>) {
+        let cube = Cube::fold(board).expect("The board does not fold into a cube");
+
+        for ((x, y), _) in board.iter().filter(|(_, &tile)| tile != Tile::None) {
+            for direction in Direction::ALL {
+                let start = (Coord::new(x as isize, y as isize), direction);
+                let mut current = start;
+
+                for _ in 0..cube.size * 4 {
+                    current = cube.ahead(current.0, current.1);
+                    assert!(board.get_at(current.0) == Some(&Tile::Open));
+                }
+
+                assert!(current == start, "From ({x}, {y}) going {direction:?}");
+            }
         }
     }
+
+    #[test]
+    fn folds_example_net() {
+        assert_loops_around(&open_board("  #\n###\n  ##", 4));
+    }
+
+    #[test]
+    fn folds_large_net() {
+        assert_loops_around(&open_board(" ##\n #\n##\n#", 50));
+    }
+
+    /// Returns the layout of a net in each of its 8 rotations and reflections.
+    fn orientations(net: &str) -> Vec<String> {
+        let squares = net
+            .lines()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.match_indices('#')
+                    .map(move |(x, _)| (x as isize, y as isize))
+            })
+            .collect::<Vec<_>>();
+
+        let mut layouts = vec![];
+
+        for transform in 0..8 {
+            let squares = squares
+                .iter()
+                .map(|&(x, y)| {
+                    let (x, y) = if transform >= 4 { (-x, y) } else { (x, y) };
+                    (0..transform % 4).fold((x, y), |(x, y), _| (-y, x))
+                })
+                .collect::<Vec<_>>();
+
+            let min_x = squares.iter().map(|i| i.0).min().unwrap();
+            let min_y = squares.iter().map(|i| i.1).min().unwrap();
+            let mut rows = vec![String::new(); 5];
+
+            for (x, y) in squares {
+                let row = &mut rows[(y - min_y) as usize];
+                let x = (x - min_x) as usize;
+
+                if row.len() <= x {
+                    *row = format!("{row:x$}#");
+                } else {
+                    row.replace_range(x..=x, "#");
+                }
+            }
+
+            layouts.push(rows.join("\n").trim_end().to_string());
+        }
+
+        layouts
+    }
+
+    #[test]
+    fn folds_every_net() {
+        for net in CUBE_NETS {
+            for layout in orientations(net) {
+                let board = open_board(&layout, 3);
+                let cube = Cube::fold(&board).unwrap_or_else(|| panic!("Not folded:\n{layout}"));
+
+                assert_eq!(cube.size, 3);
+                assert_loops_around(&board);
+
+                // Going back through an edge leads to where it was crossed
+                for (face, edges) in cube.edges.iter().enumerate() {
+                    for (&direction, edge) in Direction::ALL.iter().zip(edges) {
+                        let back = cube.edges[edge.face][side(edge.direction.opposite())];
+
+                        assert_eq!(back.face, face);
+                        assert_eq!(back.direction, direction.opposite());
+                        assert_eq!(back.reversed, edge.reversed);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn part1_without_cube() {
+        let input = Day22.parse("...\n.#.\n\n2R2L1").unwrap();
+
+        assert!(input.cube.is_none());
+        assert_eq!(Day22.part1(&input), 1004);
+        assert_eq!(Day22.part2(&input), Err(NotACube));
+    }
+
+    #[test]
+    fn rejects_other_boards() {
+        assert!(Cube::fold(&open_board("######", 3)).is_none());
+        assert!(Cube::fold(&open_board("###\n###", 3)).is_none());
+        assert!(Cube::fold(&open_board("####\n#", 3)).is_none());
+    }
 }
@@ -0,0 +1,209 @@
+use crate::error::ParseError;
+use crate::geometry::{Direction, Point2};
+use crate::grid::Grid;
+use crate::params;
+use crate::solution::Solution;
+use std::collections::{HashMap, HashSet};
+
+type Position = Point2<i32>;
+
+/// The elves, as a set of positions which can spread in any direction.
+#[derive(Clone)]
+pub struct Grove {
+    elves: HashSet<Position>,
+    /// The index of the direction considered first, in [`DIRECTIONS`].
+    first_direction: usize,
+}
+
+/// The directions considered by the elves, in order.
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+pub struct Day23 {
+    /// The number of rounds simulated in part 1.
+    pub rounds: usize,
+}
+
+impl Default for Day23 {
+    fn default() -> Self {
+        Day23 { rounds: 10 }
+    }
+}
+
+impl Solution for Day23 {
+    type Input = Grove;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Grove, ParseError> {
+        Grove::parse(input)
+    }
+
+    fn part1(&self, grove: &Grove) -> usize {
+        let mut grove = grove.clone();
+
+        for _ in 0..self.rounds {
+            grove.play_round();
+        }
+
+        grove.empty_ground()
+    }
+
+    fn part2(&self, grove: &Grove) -> usize {
+        let mut grove = grove.clone();
+        let mut rounds = 1;
+
+        while grove.play_round() {
+            rounds += 1;
+        }
+
+        rounds
+    }
+
+    params!(rounds);
+}
+
+impl Grove {
+    fn parse(input: &str) -> Result<Grove, ParseError> {
+        let map = Grid::parse(input, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(ParseError::new(format!("Invalid tile: '{c}'"))),
+        })?;
+
+        let elves = map
+            .iter()
+            .filter(|(_, &elf)| elf)
+            .map(|((x, y), _)| Position::new(x as i32, y as i32))
+            .collect::<HashSet<_>>();
+
+        if elves.is_empty() {
+            return Err(ParseError::new("No elves on the map"));
+        }
+
+        Ok(Grove {
+            elves,
+            first_direction: 0,
+        })
+    }
+
+    /// Moves the elves which have a neighbor and a free direction, unless several of them propose the same
+    /// position. Returns `false` when no elf has moved.
+    fn play_round(&mut self) -> bool {
+        let mut proposals = HashMap::new();
+
+        for &elf in &self.elves {
+            if let Some(target) = self.proposal(elf) {
+                proposals
+                    .entry(target)
+                    .and_modify(|i: &mut Option<Position>| *i = None)
+                    .or_insert(Some(elf));
+            }
+        }
+
+        let mut moved = false;
+
+        for (target, elf) in proposals {
+            if let Some(elf) = elf {
+                self.elves.remove(&elf);
+                self.elves.insert(target);
+                moved = true;
+            }
+        }
+
+        self.first_direction = (self.first_direction + 1) % DIRECTIONS.len();
+        moved
+    }
+
+    /// Returns the position an elf proposes to move to.
+    fn proposal(&self, elf: Position) -> Option<Position> {
+        let is_free = |dx, dy| !self.elves.contains(&(elf + Position::new(dx, dy)));
+
+        // Elves without any neighbor stay where they are
+        if (-1..=1).all(|dy| (-1..=1).all(|dx| (dx, dy) == (0, 0) || is_free(dx, dy))) {
+            return None;
+        }
+
+        (0..DIRECTIONS.len())
+            .map(|i| DIRECTIONS[(self.first_direction + i) % DIRECTIONS.len()])
+            .find(|&direction| {
+                let ahead = direction.delta::<i32>();
+                let side = Position::new(ahead.y, ahead.x);
+
+                [ahead - side, ahead, ahead + side]
+                    .iter()
+                    .all(|p| is_free(p.x, p.y))
+            })
+            .map(|direction| elf.step(direction))
+    }
+
+    /// Returns the number of empty tiles in the smallest rectangle which contains every elf.
+    fn empty_ground(&self) -> usize {
+        let min = self
+            .elves
+            .iter()
+            .fold(Position::new(i32::MAX, i32::MAX), |a, &b| a.min(b));
+        let max = self
+            .elves
+            .iter()
+            .fold(Position::new(i32::MIN, i32::MIN), |a, &b| a.max(b));
+        let size = max - min + Position::new(1, 1);
+
+        (size.x * size.y) as usize - self.elves.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..";
+
+    const SMALL_EXAMPLE: &str = "\
+.....
+..##.
+..#..
+.....
+..##.
+.....";
+
+    #[test]
+    fn part1_example() {
+        let input = Day23::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day23::default().part1(&input), 110);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day23::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day23::default().part2(&input), 20);
+    }
+
+    #[test]
+    fn small_example() {
+        let mut grove = Day23::default().parse(SMALL_EXAMPLE).unwrap();
+
+        for _ in 0..3 {
+            assert!(grove.play_round());
+        }
+
+        let expected = [(2, 0), (4, 1), (0, 2), (4, 3), (2, 5)]
+            .map(|(x, y)| Position::new(x, y))
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        assert!(grove.elves == expected);
+        assert!(!grove.play_round());
+    }
+}
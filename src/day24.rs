@@ -0,0 +1,325 @@
+use crate::error::ParseError;
+use crate::geometry::{Direction, Point2};
+use crate::graph::{self, NoPath, Path};
+use crate::grid::Grid;
+use crate::solution::Solution;
+use crate::trace;
+use std::iter;
+
+type Position = Point2<i32>;
+
+/// A position in the valley at a time of the blizzard cycle.
+type State = (Position, usize);
+
+/// The valley, where positions are relative to the top left tile inside the walls.
+/// The entrance is just above the first row, and the exit just below the last one.
+pub struct Valley {
+    width: usize,
+    height: usize,
+    entrance: Position,
+    exit: Position,
+    blizzards: Vec<(Position, Direction)>,
+    /// The blizzards come back to the same places after this many minutes.
+    period: usize,
+    /// Whether each tile is free of blizzards, at each minute of the period.
+    free: Vec<Grid<bool>>,
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Valley;
+    type Answer1 = Result<usize, NoPath>;
+    type Answer2 = Result<usize, NoPath>;
+
+    fn parse(&self, input: &str) -> Result<Valley, ParseError> {
+        Valley::parse(input)
+    }
+
+    fn part1(&self, valley: &Valley) -> Result<usize, NoPath> {
+        self.travel(valley, &[valley.entrance, valley.exit])
+    }
+
+    fn part2(&self, valley: &Valley) -> Result<usize, NoPath> {
+        self.travel(valley, &valley.round_trip())
+    }
+}
+
+impl Day24 {
+    /// Goes through the given positions in order, and returns the time it takes.
+    fn travel(&self, valley: &Valley, stops: &[Position]) -> Result<usize, NoPath> {
+        let legs = valley.travel(stops).ok_or(NoPath)?;
+        let mut time = 0;
+
+        for path in legs {
+            valley.trace_route(&path, time);
+            time += path.cost;
+        }
+
+        Ok(time)
+    }
+}
+
+impl Valley {
+    fn parse(input: &str) -> Result<Valley, ParseError> {
+        let map = Grid::parse(input, |c| match c {
+            '#' | '.' | '>' | '<' | '^' | 'v' => Ok(c),
+            _ => Err(ParseError::new(format!("Invalid tile: '{c}'"))),
+        })?;
+
+        if map.width() < 3 || map.height() < 3 {
+            return Err(ParseError::new("The valley is too small"));
+        }
+
+        let opening = |y: usize| {
+            let openings = (0..map.width())
+                .filter(|&x| map[(x, y)] != '#')
+                .collect::<Vec<_>>();
+
+            match openings[..] {
+                [x] if x > 0 && x < map.width() - 1 => Ok(x as i32 - 1),
+                _ => Err(ParseError::new("Expected a single opening in the wall").at_line(y + 1)),
+            }
+        };
+
+        let entrance = Position::new(opening(0)?, -1);
+        let exit = Position::new(opening(map.height() - 1)?, map.height() as i32 - 2);
+
+        let (width, height) = (map.width() - 2, map.height() - 2);
+        let mut blizzards = vec![];
+
+        for y in 1..=height {
+            for x in [0, width + 1] {
+                if map[(x, y)] != '#' {
+                    return Err(ParseError::new("Expected a wall")
+                        .at_line(y + 1)
+                        .at_column(x + 1));
+                }
+            }
+
+            for x in 1..=width {
+                let direction = match map[(x, y)] {
+                    '>' => Direction::Right,
+                    '<' => Direction::Left,
+                    '^' => Direction::Up,
+                    'v' => Direction::Down,
+                    '.' => continue,
+                    _ => {
+                        return Err(ParseError::new("Unexpected wall")
+                            .at_line(y + 1)
+                            .at_column(x + 1))
+                    }
+                };
+
+                blizzards.push((Position::new(x as i32 - 1, y as i32 - 1), direction));
+            }
+        }
+
+        let period = lcm(width, height);
+
+        let mut valley = Valley {
+            width,
+            height,
+            entrance,
+            exit,
+            blizzards,
+            period,
+            free: vec![],
+        };
+
+        valley.free = (0..period)
+            .map(|time| {
+                let mut free = Grid::new(width, height, true);
+
+                for position in valley.blizzard_positions(time) {
+                    free[position] = false;
+                }
+
+                free
+            })
+            .collect();
+
+        Ok(valley)
+    }
+
+    /// Returns the position of each blizzard at the given time, as they wrap around the valley.
+    fn blizzard_positions(&self, time: usize) -> impl Iterator<Item = Position> + '_ {
+        let (width, height) = (self.width as i32, self.height as i32);
+
+        self.blizzards.iter().map(move |&(position, direction)| {
+            let delta = direction.delta::<i32>();
+            let steps = (time % self.period) as i32;
+
+            Position::new(
+                (position.x + delta.x * steps).rem_euclid(width),
+                (position.y + delta.y * steps).rem_euclid(height),
+            )
+        })
+    }
+
+    /// Returns the trip of part 2, from the entrance to the exit and back, then to the exit again.
+    fn round_trip(&self) -> [Position; 4] {
+        [self.entrance, self.exit, self.entrance, self.exit]
+    }
+
+    /// Returns whether the round trip can be made through the blizzards.
+    pub(crate) fn can_travel(&self) -> bool {
+        self.travel(&self.round_trip()).is_some()
+    }
+
+    /// Finds the quickest way through the given positions in order, leg by leg,
+    /// or returns `None` when the blizzards block one of them.
+    fn travel(&self, stops: &[Position]) -> Option<Vec<Path<State, usize>>> {
+        let mut legs = vec![];
+        let mut time = 0;
+
+        for leg in stops.windows(2) {
+            let path = self.cross(leg[0], leg[1], time)?;
+            time += path.cost;
+            legs.push(path);
+        }
+
+        Some(legs)
+    }
+
+    /// Finds the quickest way from `from` to `to`, leaving at the given time. Since the blizzards move in cycles,
+    /// the same position at times which are a period apart is the same state.
+    fn cross(&self, from: Position, to: Position, time: usize) -> Option<Path<State, usize>> {
+        graph::bfs_to(
+            (from, time % self.period),
+            |&(position, time)| {
+                let time = (time + 1) % self.period;
+
+                iter::once(position)
+                    .chain(Direction::ALL.map(|i| position.step(i)))
+                    .filter(move |&i| self.is_free(i, time))
+                    .map(move |i| (i, time))
+            },
+            |&(position, _)| position == to,
+        )
+    }
+
+    fn is_free(&self, position: Position, time: usize) -> bool {
+        if position == self.entrance || position == self.exit {
+            return true;
+        }
+
+        self.free[time].get_at(position).copied().unwrap_or(false)
+    }
+
+    /// Prints the route of the expedition minute by minute, when tracing is enabled.
+    fn trace_route(&self, path: &Path<State, usize>, start_time: usize) {
+        for (minute, window) in path.nodes.windows(2).enumerate() {
+            let ((from, _), (to, _)) = (window[0], window[1]);
+            let minute = start_time + minute + 1;
+
+            let action = match Direction::ALL.into_iter().find(|&i| from.step(i) == to) {
+                Some(Direction::Up) => "move up",
+                Some(Direction::Down) => "move down",
+                Some(Direction::Left) => "move left",
+                Some(Direction::Right) => "move right",
+                None => "wait",
+            };
+
+            trace!("Minute {minute}, {action}:");
+            trace!("{}", self.draw(minute, to));
+        }
+    }
+
+    /// Draws the valley like in the puzzle text, with the expedition as `E`.
+    fn draw(&self, time: usize, expedition: Position) -> String {
+        let mut map = Grid::from_fn(self.width + 2, self.height + 2, |x, y| {
+            let position = Position::new(x as i32 - 1, y as i32 - 1);
+            let is_wall = x == 0 || y == 0 || x == self.width + 1 || y == self.height + 1;
+
+            match is_wall && position != self.entrance && position != self.exit {
+                true => '#',
+                false => '.',
+            }
+        });
+
+        for (position, &(_, direction)) in self.blizzard_positions(time).zip(&self.blizzards) {
+            let tile = &mut map[position + Position::new(1, 1)];
+
+            *tile = match *tile {
+                '.' => match direction {
+                    Direction::Up => '^',
+                    Direction::Down => 'v',
+                    Direction::Left => '<',
+                    Direction::Right => '>',
+                },
+                c => c
+                    .to_digit(10)
+                    .map_or('2', |i| char::from_digit(i + 1, 10).unwrap_or('9')),
+            };
+        }
+
+        map[expedition + Position::new(1, 1)] = 'E';
+        map.to_string()
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    let gcd = |mut a: usize, mut b: usize| {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    };
+
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+
+    #[test]
+    fn part1_example() {
+        let input = Day24.parse(EXAMPLE).unwrap();
+        assert_eq!(Day24.part1(&input), Ok(18));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day24.parse(EXAMPLE).unwrap();
+        assert_eq!(Day24.part2(&input), Ok(54));
+    }
+
+    #[test]
+    fn blocked_valley() {
+        // The only tile of the valley always has a blizzard, which keeps going around it
+        let input = Day24.parse("#.#\n#<#\n#.#").unwrap();
+        assert_eq!(Day24.part1(&input), Err(NoPath));
+    }
+
+    #[test]
+    fn draws_blizzards() {
+        let valley = Day24.parse(EXAMPLE).unwrap();
+
+        assert_eq!(valley.period, 12);
+        assert_eq!(
+            valley.draw(0, valley.entrance),
+            EXAMPLE.replacen('.', "E", 1) + "\n"
+        );
+        assert_eq!(
+            valley.draw(1, Position::new(0, 0)),
+            "\
+#.######
+#E>3.<.#
+#<..<<.#
+#>2.22.#
+#>v..^<#
+######.#
+"
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use crate::common::{Part, Parts};
+use crate::error::{parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+use std::convert::Infallible;
+use std::fmt::{self, Display};
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// A number in balanced base 5, where each digit goes from -2 to 2, written `=`, `-`, `0`, `1` and `2`.
+///
+/// Numbers are added digit by digit, so that sums are not limited to the range of an integer type.
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub struct Snafu {
+    /// The digits from the least significant one, without leading zeros, so that zero has no digits at all.
+    digits: Vec<i8>,
+}
+
+/// The error returned when a SNAFU number does not fit in an integer type.
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutOfRange;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Vec<Snafu>;
+    type Answer1 = Snafu;
+    type Answer2 = Infallible;
+
+    const PARTS: Parts = Parts::Only(Part::One);
+
+    fn parse(&self, input: &str) -> Result<Vec<Snafu>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, numbers: &Vec<Snafu>) -> Snafu {
+        numbers.iter().sum()
+    }
+
+    /// Adds the numbers in decimal, when they fit.
+    fn reference(&self, numbers: &Vec<Snafu>, part: Part) -> Option<String> {
+        if part != Part::One {
+            return None;
+        }
+
+        let mut sum = 0i128;
+
+        for number in numbers {
+            sum = sum.checked_add(i128::try_from(number).ok()?)?;
+        }
+
+        Some(Snafu::from(sum).to_string())
+    }
+}
+
+impl Snafu {
+    const BASE: i8 = 5;
+
+    fn from_digits(mut digits: Vec<i8>) -> Snafu {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Snafu { digits }
+    }
+
+    /// Converts to an integer, returning `None` on overflow.
+    fn to_integer(&self) -> Option<i128> {
+        let base = Snafu::BASE as i128;
+
+        self.digits.iter().rev().try_fold(0i128, |value, &digit| {
+            // When the digit has the other sign, it is borrowed from the value first, so that values
+            // at the ends of the range do not overflow before the digit is added
+            let (value, digit) = match (value.signum(), digit.signum()) {
+                (-1, 1) => (value + 1, digit as i128 - base),
+                (1, -1) => (value - 1, digit as i128 + base),
+                _ => (value, digit as i128),
+            };
+
+            value.checked_mul(base)?.checked_add(digit)
+        })
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::new("Empty number"));
+        }
+
+        let digits = s
+            .chars()
+            .rev()
+            .enumerate()
+            .map(|(index, c)| match c {
+                '=' => Ok(-2),
+                '-' => Ok(-1),
+                '0' => Ok(0),
+                '1' => Ok(1),
+                '2' => Ok(2),
+                _ => Err(ParseError::new(format!("Invalid digit: '{c}'"))
+                    .at_column(s.chars().count() - index)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Snafu::from_digits(digits))
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.digits.is_empty() {
+            return write!(f, "0");
+        }
+
+        for &digit in self.digits.iter().rev() {
+            let c = match digit {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                _ => '2',
+            };
+
+            write!(f, "{c}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Answer for Snafu {
+    fn to_answer(&self) -> Result<String, String> {
+        Ok(self.to_string())
+    }
+}
+
+impl From<i128> for Snafu {
+    fn from(mut value: i128) -> Self {
+        let mut digits = vec![];
+
+        while value != 0 {
+            // Digits above 2 are written as a negative digit, carrying one to the next position.
+            // The remainders are taken first so that nothing overflows at the ends of the range.
+            let base = Snafu::BASE as i128;
+            let digit = (value.rem_euclid(base) + 2) % base - 2;
+            digits.push(digit as i8);
+            value = value / base + (value % base - digit) / base;
+        }
+
+        Snafu::from_digits(digits)
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(value: i64) -> Self {
+        Snafu::from(value as i128)
+    }
+}
+
+impl TryFrom<&Snafu> for i128 {
+    type Error = OutOfRange;
+
+    fn try_from(value: &Snafu) -> Result<Self, Self::Error> {
+        value.to_integer().ok_or(OutOfRange)
+    }
+}
+
+impl TryFrom<&Snafu> for i64 {
+    type Error = OutOfRange;
+
+    fn try_from(value: &Snafu) -> Result<Self, Self::Error> {
+        let value = i128::try_from(value)?;
+        i64::try_from(value).map_err(|_| OutOfRange)
+    }
+}
+
+impl Add for &Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: &Snafu) -> Snafu {
+        let len = self.digits.len().max(other.digits.len());
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+
+        for i in 0..len {
+            let a = self.digits.get(i).copied().unwrap_or(0);
+            let b = other.digits.get(i).copied().unwrap_or(0);
+
+            // The sum goes from -5 to 5, which is brought back to a digit from -2 to 2 with a carry
+            let sum = a + b + carry;
+            let digit = (sum + 2).rem_euclid(Snafu::BASE) - 2;
+
+            digits.push(digit);
+            carry = (sum - digit) / Snafu::BASE;
+        }
+
+        digits.push(carry);
+        Snafu::from_digits(digits)
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: Snafu) -> Snafu {
+        &self + &other
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), |a, b| &a + b)
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Snafu>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), |a, b| a + b)
+    }
+}
+
+impl Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The SNAFU number is out of range")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122";
+
+    #[test]
+    fn part1_example() {
+        let input = Day25.parse(EXAMPLE).unwrap();
+        let sum = Day25.part1(&input);
+
+        assert_eq!(sum.to_string(), "2=-1=0");
+        assert_eq!(i64::try_from(&sum), Ok(4890));
+    }
+
+    #[test]
+    fn converts_numbers() {
+        let numbers = [
+            (0i64, "0"),
+            (1, "1"),
+            (3, "1="),
+            (8, "2="),
+            (10, "20"),
+            (15, "1=0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+            (-1, "-"),
+            (-2022, "-2--1="),
+        ];
+
+        for (decimal, snafu) in numbers {
+            assert_eq!(Snafu::from(decimal).to_string(), snafu);
+            assert_eq!(i64::try_from(&snafu.parse::<Snafu>().unwrap()), Ok(decimal));
+        }
+
+        for value in [i64::MIN, i64::MAX] {
+            assert_eq!(i64::try_from(&Snafu::from(value)), Ok(value));
+        }
+
+        for value in [i128::MIN, i128::MAX] {
+            assert_eq!(i128::try_from(&Snafu::from(value)), Ok(value));
+        }
+
+        assert_eq!("00012".parse::<Snafu>().unwrap().to_string(), "12");
+        assert!("12a".parse::<Snafu>().is_err());
+    }
+
+    #[test]
+    fn adds_beyond_integer_range() {
+        let max = Snafu::from(i64::MAX);
+        assert_eq!(i64::try_from(&(&max + &max)), Err(OutOfRange));
+        assert_eq!(i128::try_from(&(&max + &max)), Ok(i64::MAX as i128 * 2));
+
+        let max = Snafu::from(i128::MAX);
+        let sum = [&max, &max, &Snafu::from(2i64)].into_iter().sum::<Snafu>();
+        assert_eq!(i128::try_from(&sum), Err(OutOfRange));
+
+        // 2 * (i128::MAX + 1) is 2^128, and then going back to i128::MIN cancels out the high digits
+        let sum = sum + Snafu::from(i128::MIN) + Snafu::from(i128::MIN);
+        assert_eq!(sum, Snafu::from(0i64));
+    }
+
+    #[test]
+    fn adds_opposite_numbers() {
+        for value in [1i64, 7, 2022, 314159265] {
+            let sum = Snafu::from(value) + Snafu::from(-value);
+            assert_eq!(sum.to_string(), "0");
+        }
+    }
+}
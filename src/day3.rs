@@ -1,58 +1,111 @@
-use crate::common::get_input_lines;
+use crate::error::ParseError;
+use crate::solution::Solution;
 use std::collections::HashSet;
 
-#[allow(dead_code)]
-pub fn run() {
-    let input = get_input_lines();
-
-    let result = input
-        .iter()
-        .map(|i| {
-            let compartments = i.split_at(i.len() / 2);
-            let a = compartments.0.bytes().collect::<HashSet<_>>();
-            let b = compartments.1.bytes().collect::<HashSet<_>>();
-            a.intersection(&b).copied().next().unwrap()
-        })
-        .map(|i| get_score(i))
-        .sum::<i32>();
-
-    println!("Result (part 1): {}", result);
-
-    let result = input
-        .iter()
-        .collect::<Vec<_>>()
-        .chunks(3)
-        .map(|group| {
-            let item = group
-                .iter()
-                .map(|i| i.bytes().collect::<HashSet<u8>>())
-                .fold(None::<HashSet<u8>>, |acc, i| {
-                    Some(if let Some(set) = acc {
-                        set.intersection(&i).copied().collect::<HashSet<u8>>()
-                    } else {
-                        i
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        let mut rucksacks = vec![];
+
+        for (index, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(pos) = line.find(|c: char| !c.is_ascii_alphabetic()) {
+                return Err(ParseError::new("Expected a letter")
+                    .at_column(pos + 1)
+                    .at_line(index + 1));
+            }
+
+            if line.len() % 2 != 0 {
+                return Err(ParseError::new("Expected an even number of items").at_line(index + 1));
+            }
+
+            rucksacks.push(line.to_string());
+        }
+
+        Ok(rucksacks)
+    }
+
+    fn part1(&self, input: &Vec<String>) -> i32 {
+        input
+            .iter()
+            .map(|i| {
+                let compartments = i.split_at(i.len() / 2);
+                let a = compartments.0.bytes().collect::<HashSet<_>>();
+                let b = compartments.1.bytes().collect::<HashSet<_>>();
+                a.intersection(&b).copied().next().unwrap()
+            })
+            .map(get_score)
+            .sum::<i32>()
+    }
+
+    fn part2(&self, input: &Vec<String>) -> i32 {
+        input
+            .iter()
+            .collect::<Vec<_>>()
+            .chunks(3)
+            .map(|group| {
+                let item = group
+                    .iter()
+                    .map(|i| i.bytes().collect::<HashSet<u8>>())
+                    .fold(None::<HashSet<u8>>, |acc, i| {
+                        Some(if let Some(set) = acc {
+                            set.intersection(&i).copied().collect::<HashSet<u8>>()
+                        } else {
+                            i
+                        })
                     })
-                })
-                .into_iter()
-                .next()
-                .unwrap()
-                .into_iter()
-                .next()
-                .unwrap();
-
-            get_score(item)
-        })
-        .sum::<i32>();
-
-    println!("Result (part 2): {}", result);
+                    .into_iter()
+                    .next()
+                    .unwrap()
+                    .into_iter()
+                    .next()
+                    .unwrap();
+
+                get_score(item)
+            })
+            .sum::<i32>()
+    }
 }
 
 fn get_score(i: u8) -> i32 {
-    if (b'a'..=b'z').contains(&i) {
+    if i.is_ascii_lowercase() {
         (i - b'a' + 1) as i32
-    } else if (b'A'..=b'Z').contains(&i) {
+    } else if i.is_ascii_uppercase() {
         (i - b'A' + 27) as i32
     } else {
-        panic!()
+        unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn part1_example() {
+        let input = Day3.parse(EXAMPLE).unwrap();
+        assert_eq!(Day3.part1(&input), 157);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day3.parse(EXAMPLE).unwrap();
+        assert_eq!(Day3.part2(&input), 70);
     }
 }
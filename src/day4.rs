@@ -1,48 +1,83 @@
-use crate::common::get_input_lines;
-use lazy_static::lazy_static;
-use regex::Regex;
+use crate::error::{parse_lines, ParseError};
+use crate::interval::IntervalSet;
+use crate::parse::scan;
+use crate::solution::Solution;
 use std::ops::RangeInclusive;
+use std::str::FromStr;
 
-struct InputItem(RangeInclusive<i32>, RangeInclusive<i32>);
+pub struct InputItem(RangeInclusive<i32>, RangeInclusive<i32>);
 
-#[allow(dead_code)]
-pub fn run() {
-    let input = get_input_lines()
-        .iter()
-        .map(|i| i.as_str().into())
-        .collect::<Vec<InputItem>>();
+pub struct Day4;
 
-    let result = input.iter().filter(|i| i.is_fully_contained()).count();
+impl Solution for Day4 {
+    type Input = Vec<InputItem>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    println!("Result (part 1): {}", result);
+    fn parse(&self, input: &str) -> Result<Vec<InputItem>, ParseError> {
+        parse_lines(input)
+    }
 
-    let result = input.iter().filter(|i| i.has_overlap()).count();
+    fn part1(&self, input: &Vec<InputItem>) -> usize {
+        input.iter().filter(|i| i.is_fully_contained()).count()
+    }
 
-    println!("Result (part 2): {}", result);
+    fn part2(&self, input: &Vec<InputItem>) -> usize {
+        input.iter().filter(|i| i.has_overlap()).count()
+    }
 }
 
 impl InputItem {
     pub fn is_fully_contained(&self) -> bool {
-        self.0.start() <= self.1.start() && self.0.end() >= self.1.end()
-            || self.1.start() <= self.0.start() && self.1.end() >= self.0.end()
+        let (a, b) = self.sets();
+        let common = a.intersection(&b);
+        common == a || common == b
     }
 
     pub fn has_overlap(&self) -> bool {
-        self.0.end() >= self.1.start() && self.0.start() <= self.1.end()
+        let (a, b) = self.sets();
+        !a.intersection(&b).is_empty()
+    }
+
+    fn sets(&self) -> (IntervalSet<i32>, IntervalSet<i32>) {
+        (self.0.clone().into(), self.1.clone().into())
+    }
+}
+
+impl FromStr for InputItem {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut fields = scan(line, "{}-{},{}-{}")?;
+
+        Ok(Self(
+            fields.parse()?..=fields.parse()?,
+            fields.parse()?..=fields.parse()?,
+        ))
     }
 }
 
-impl From<&str> for InputItem {
-    fn from(line: &str) -> Self {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"^([0-9]+)-([0-9]+),([0-9]+)-([0-9]+)$").unwrap();
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
 
-        let cap = RE.captures(line).unwrap();
+    #[test]
+    fn part1_example() {
+        let input = Day4.parse(EXAMPLE).unwrap();
+        assert_eq!(Day4.part1(&input), 2);
+    }
 
-        Self(
-            cap[1].parse::<i32>().unwrap()..=cap[2].parse::<i32>().unwrap(),
-            cap[3].parse::<i32>().unwrap()..=cap[4].parse::<i32>().unwrap(),
-        )
+    #[test]
+    fn part2_example() {
+        let input = Day4.parse(EXAMPLE).unwrap();
+        assert_eq!(Day4.part2(&input), 4);
     }
 }
@@ -1,8 +1,11 @@
-use crate::common::*;
-use regex::Regex;
+use crate::common::Part;
+use crate::error::ParseError;
+use crate::parse::scan_line;
+use crate::render::{Canvas, Color, Frames};
+use crate::solution::Solution;
 use std::fmt::{Display, Formatter};
 
-struct Input {
+pub struct Input {
     stacks: Stacks,
     moves: Vec<Move>,
 }
@@ -22,68 +25,105 @@ struct Move {
     to: usize,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let input = get_input();
+pub struct Day5;
 
-    let mut stacks = input.stacks.clone();
-    for m in input.moves.iter() {
-        stacks.apply_move(&m);
+impl Solution for Day5 {
+    type Input = Input;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(&self, input: &str) -> Result<Input, ParseError> {
+        get_input(input)
     }
 
-    let result = stacks.get_top_crates();
-    println!("Result (part 1): {result}");
+    fn part1(&self, input: &Input) -> String {
+        let mut stacks = input.stacks.clone();
+        for m in input.moves.iter() {
+            stacks.apply_move(m);
+        }
 
-    let mut stacks = input.stacks.clone();
-    for m in input.moves.iter() {
-        stacks.apply_move_multi(&m);
+        stacks.get_top_crates()
     }
 
-    let result = stacks.get_top_crates();
-    println!("Result (part 2): {result}");
-}
+    fn part2(&self, input: &Input) -> String {
+        let mut stacks = input.stacks.clone();
+        for m in input.moves.iter() {
+            stacks.apply_move_multi(m);
+        }
+
+        stacks.get_top_crates()
+    }
+
+    fn render(&self, input: &Input, part: Part, frames: &mut Frames) -> bool {
+        let mut stacks = input.stacks.clone();
+        let height = stacks.crate_count() + 1;
+        frames.push(|| stacks.draw(height));
 
-fn get_input() -> Input {
-    let regex = Regex::new(
-        r"(?x)
-        \[(?P<crate>[A-Z])]
-        | move \s (?P<move_count>[0-9]+) \s from \s (?P<from>[0-9]+) \s to \s (?P<to>[0-9]+)",
-    )
-    .unwrap();
+        for m in input.moves.iter() {
+            match part {
+                Part::One => stacks.apply_move(m),
+                Part::Two => stacks.apply_move_multi(m),
+            }
+
+            frames.push(|| stacks.draw(height));
+        }
+
+        frames.finish(|| stacks.draw(height));
+        true
+    }
+}
 
+fn get_input(input: &str) -> Result<Input, ParseError> {
     let mut stacks = vec![];
     let mut moves = vec![];
 
-    for line in get_input_lines() {
-        for capture in regex.captures_iter(&line) {
-            if let Some(m) = capture.name("crate") {
-                let name = Crate(m.as_str().chars().next().unwrap());
-                let index = (m.start() - 1) / 4;
-
-                let mut stack = stacks.get_mut(index);
-                if stack.is_none() {
-                    stacks.resize_with(index + 1, || CrateStack(vec![]));
-                    stack = stacks.get_mut(index);
-                }
-
-                let stack = stack.unwrap();
-                stack.push(name)
-            } else if let Some(m) = capture.name("move_count") {
-                let count = m.as_str().parse::<usize>().unwrap();
-                let from = capture
-                    .name("from")
-                    .unwrap()
-                    .as_str()
-                    .parse::<usize>()
-                    .unwrap();
-                let to = capture
-                    .name("to")
-                    .unwrap()
-                    .as_str()
-                    .parse::<usize>()
-                    .unwrap();
-                moves.push(Move { count, from, to })
+    for (index, line) in input.lines().enumerate() {
+        if line.starts_with("move") {
+            let stack_count = stacks.len();
+
+            let m = scan_line((index, line), "move {} from {} to {}", |mut fields| {
+                let count = fields.parse()?;
+
+                let mut stack_index = || match fields.parse::<usize>()? {
+                    i if (1..=stack_count).contains(&i) => Ok(i),
+                    _ => Err(fields.error("No such stack")),
+                };
+
+                Ok(Move {
+                    count,
+                    from: stack_index()?,
+                    to: stack_index()?,
+                })
+            })?;
+
+            moves.push(m);
+            continue;
+        }
+
+        // The stack numbers below the crates also account for the stacks which start empty
+        if line.trim_start().starts_with(|c: char| c.is_ascii_digit()) {
+            let count = line.split_whitespace().count();
+
+            if count > stacks.len() {
+                stacks.resize_with(count, || CrateStack(vec![]));
             }
+
+            continue;
+        }
+
+        // Crates are drawn as [A], in columns 4 characters wide
+        for (column, c) in line.chars().enumerate() {
+            if column % 4 != 1 || !c.is_ascii_uppercase() {
+                continue;
+            }
+
+            let stack_index = column / 4;
+
+            if stack_index >= stacks.len() {
+                stacks.resize_with(stack_index + 1, || CrateStack(vec![]));
+            }
+
+            stacks[stack_index].push(Crate(c));
         }
     }
 
@@ -91,10 +131,10 @@ fn get_input() -> Input {
         stack.0.reverse()
     }
 
-    Input {
+    Ok(Input {
         stacks: Stacks(stacks),
         moves,
-    }
+    })
 }
 
 impl Stacks {
@@ -119,6 +159,31 @@ impl Stacks {
     }
 }
 
+impl Stacks {
+    fn crate_count(&self) -> usize {
+        self.0.iter().map(|i| i.0.len()).sum()
+    }
+
+    /// Draws the stacks side by side on the floor, with a color for each crate name,
+    /// in a picture tall enough for `height` rows.
+    fn draw(&self, height: usize) -> Canvas {
+        let mut canvas = Canvas::new(self.0.len() * 2 + 1, height, Color::BLACK);
+
+        for x in 0..canvas.width() {
+            canvas.set(x, height - 1, Color::GRAY);
+        }
+
+        for (i, stack) in self.0.iter().enumerate() {
+            for (j, item) in stack.0.iter().enumerate() {
+                let hue = (item.name() as u32 - 'A' as u32) * 7 % 26;
+                canvas.set(i * 2 + 1, height - 2 - j, Color::hue(hue as f64 / 26.0));
+            }
+        }
+
+        canvas
+    }
+}
+
 impl Display for Stacks {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.get_top_crates())
@@ -148,3 +213,39 @@ impl Crate {
         self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r"    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+    #[test]
+    fn part1_example() {
+        let input = Day5.parse(EXAMPLE).unwrap();
+        assert_eq!(Day5.part1(&input), "CMZ");
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day5.parse(EXAMPLE).unwrap();
+        assert_eq!(Day5.part2(&input), "MCD");
+    }
+
+    #[test]
+    fn stack_which_starts_empty() {
+        let input = Day5
+            .parse("[A]\n[B] [C]\n 1   2   3\n\nmove 1 from 1 to 3")
+            .unwrap();
+        assert_eq!(Day5.part1(&input), "BCA");
+        assert_eq!(Day5.part2(&input), "BCA");
+    }
+}
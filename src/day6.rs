@@ -1,15 +1,28 @@
-use crate::common::get_input_lines;
+use crate::error::ParseError;
+use crate::solution::Solution;
 use std::collections::{HashSet, VecDeque};
 
-#[allow(dead_code)]
-pub fn run() {
-    let input = get_input_lines().into_iter().next().unwrap();
+pub struct Day6;
 
-    let result = find_start(&input, 4);
-    println!("Result (part 1): {result}");
+impl Solution for Day6 {
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let result = find_start(&input, 14);
-    println!("Result (part 2): {result}");
+    fn parse(&self, input: &str) -> Result<String, ParseError> {
+        match input.lines().next() {
+            Some(line) if !line.is_empty() => Ok(line.to_string()),
+            _ => Err(ParseError::new("Empty datastream").at_line(1)),
+        }
+    }
+
+    fn part1(&self, input: &String) -> usize {
+        find_start(input, 4)
+    }
+
+    fn part2(&self, input: &String) -> usize {
+        find_start(input, 14)
+    }
 }
 
 fn find_start(input: &str, length: usize) -> usize {
@@ -17,14 +30,15 @@ fn find_start(input: &str, length: usize) -> usize {
     let mut set = HashSet::with_capacity(length);
 
     for (index, c) in input.chars().enumerate() {
-        if buffer.len() < length {
-            buffer.push_back(c);
+        // Every window is checked as soon as it is full, the first one included
+        buffer.push_back(c);
+
+        if buffer.len() > length {
+            buffer.pop_front();
+        } else if buffer.len() < length {
             continue;
         }
 
-        buffer.pop_front();
-        buffer.push_back(c);
-
         set.clear();
         set.extend(buffer.iter().copied());
 
@@ -35,3 +49,46 @@ fn find_start(input: &str, length: usize) -> usize {
 
     panic!("Not found")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, usize, usize); 5] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn part1_examples() {
+        for (example, expected, _) in EXAMPLES {
+            let input = Day6.parse(example).unwrap();
+            assert_eq!(Day6.part1(&input), expected);
+        }
+    }
+
+    #[test]
+    fn marker_at_the_start() {
+        let input = Day6.parse("abcdefghijklmnab").unwrap();
+        assert_eq!(Day6.part1(&input), 4);
+        assert_eq!(Day6.part2(&input), 14);
+    }
+
+    #[test]
+    fn marker_at_the_end() {
+        let input = Day6.parse("aaaaaaaaaaaaaabcdefghijklmn").unwrap();
+        assert_eq!(Day6.part1(&input), 17);
+        assert_eq!(Day6.part2(&input), 27);
+    }
+
+    #[test]
+    fn part2_examples() {
+        for (example, _, expected) in EXAMPLES {
+            let input = Day6.parse(example).unwrap();
+            assert_eq!(Day6.part2(&input), expected);
+        }
+    }
+}
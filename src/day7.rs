@@ -1,6 +1,8 @@
-use crate::common::get_input_lines;
+use crate::error::{parse_token, ParseError};
+use crate::params;
+use crate::solution::Solution;
 
-struct FileSystem {
+pub struct FileSystem {
     entries: Vec<Entry>,
 }
 
@@ -9,6 +11,7 @@ enum Entry {
     Dir(Dir),
 }
 
+#[allow(dead_code)]
 #[derive(Copy, Clone)]
 struct FileHandle(usize);
 
@@ -27,22 +30,49 @@ struct Dir {
     contents: Vec<usize>,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let input = parse(get_input_lines().iter());
+pub struct Day7 {
+    /// The size of the largest directories counted in part 1.
+    pub small_dir_size: usize,
+    pub disk_size: usize,
+    /// The free space needed for the update in part 2.
+    pub required_space: usize,
+}
+
+impl Default for Day7 {
+    fn default() -> Self {
+        Day7 {
+            small_dir_size: 100_000,
+            disk_size: 70_000_000,
+            required_space: 30_000_000,
+        }
+    }
+}
+
+impl Solution for Day7 {
+    type Input = FileSystem;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<FileSystem, ParseError> {
+        parse(input)
+    }
 
-    let result = part1(&input);
-    println!("Result (part 1): {result}");
+    fn part1(&self, fs: &FileSystem) -> usize {
+        part1(fs, self.small_dir_size)
+    }
+
+    fn part2(&self, fs: &FileSystem) -> usize {
+        part2(fs, self.disk_size, self.required_space)
+    }
 
-    let result = part2(&input);
-    println!("Result (part 2): {result}");
+    params!(small_dir_size, disk_size, required_space);
 }
 
-fn part1(fs: &FileSystem) -> usize {
+fn part1(fs: &FileSystem, small_dir_size: usize) -> usize {
     let mut result = 0;
 
     fs.traverse_get_size(|size| {
-        if size <= 100000 {
+        if size <= small_dir_size {
             result += size;
         }
     });
@@ -50,10 +80,10 @@ fn part1(fs: &FileSystem) -> usize {
     result
 }
 
-fn part2(fs: &FileSystem) -> usize {
+fn part2(fs: &FileSystem, disk_size: usize, required_space: usize) -> usize {
     let used_size = fs.traverse_get_size(|_| {});
-    let unused_size = 70000000 - used_size;
-    let required_size = 30000000 - unused_size;
+    let unused_size = disk_size.saturating_sub(used_size);
+    let required_size = required_space.saturating_sub(unused_size);
 
     let mut result = usize::MAX;
 
@@ -66,7 +96,7 @@ fn part2(fs: &FileSystem) -> usize {
     result
 }
 
-fn parse<T: AsRef<str>>(script: impl Iterator<Item = T>) -> FileSystem {
+fn parse(script: &str) -> Result<FileSystem, ParseError> {
     let mut fs = FileSystem::new();
     let mut dirs = vec![fs.get_root()];
 
@@ -76,32 +106,38 @@ fn parse<T: AsRef<str>>(script: impl Iterator<Item = T>) -> FileSystem {
         };
     }
 
-    for line in script {
-        let line = line.as_ref().split_whitespace().collect::<Vec<_>>();
+    for (index, line) in script.lines().enumerate() {
+        let error = |token, message| ParseError::at(line, token, message).at_line(index + 1);
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
 
-        match line[..] {
+        match tokens[..] {
             ["$", "cd", "/"] => {
                 dirs.clear();
                 dirs.push(fs.get_root());
             }
             ["$", "cd", ".."] => {
-                dirs.pop();
-            }
-            ["$", "cd", name] => {
-                dirs.push(fs.get_subdir(cd!(), name));
+                if dirs.len() > 1 {
+                    dirs.pop();
+                }
             }
+            ["$", "cd", name] => match fs.get_subdir(cd!(), name) {
+                Some(dir) => dirs.push(dir),
+                None => return Err(error(name, "Directory not found")),
+            },
             ["$", "ls"] => {}
             ["dir", name] => {
                 fs.add_dir(cd!(), name);
             }
             [size, name] => {
-                fs.add_file(cd!(), name, size.parse().unwrap());
+                let size = parse_token(line, size).map_err(|e| e.at_line(index + 1))?;
+                fs.add_file(cd!(), name, size);
             }
-            _ => panic!("Invalid input"),
+            [] => {}
+            _ => return Err(error(line, "Invalid command or listing")),
         }
     }
 
-    fs
+    Ok(fs)
 }
 
 impl FileSystem {
@@ -142,16 +178,16 @@ impl FileSystem {
         self.get_dir_mut(dir).contents.push(id.0);
     }
 
-    fn get_subdir(&self, dir: DirHandle, name: &str) -> DirHandle {
+    fn get_subdir(&self, dir: DirHandle, name: &str) -> Option<DirHandle> {
         for entry in self.iter_dir(dir) {
             if let Entry::Dir(dir) = entry {
                 if dir.id.1 == name {
-                    return DirHandle(dir.id.0);
+                    return Some(DirHandle(dir.id.0));
                 }
             }
         }
 
-        panic!("Dir not found")
+        None
     }
 
     fn get_dir(&self, dir: DirHandle) -> &Dir {
@@ -223,3 +259,45 @@ impl Dir {
         DirHandle(self.id.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    #[test]
+    fn part1_example() {
+        let input = Day7::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::default().part1(&input), 95437);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day7::default().parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::default().part2(&input), 24933642);
+    }
+}
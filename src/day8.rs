@@ -1,31 +1,41 @@
-use crate::common::get_input_lines;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solution::Solution;
 use std::collections::HashSet;
 
-struct Field(Vec<Vec<i32>>);
+pub struct Field(Grid<i32>);
 
-#[allow(dead_code)]
-pub fn run() {
-    let input = parse(&get_input_lines());
+pub struct Day8;
 
-    let result = input.get_visible_trees();
-    println!("Result (part 1): {result}");
+impl Solution for Day8 {
+    type Input = Field;
+    type Answer1 = usize;
+    type Answer2 = i32;
 
-    let result = input.get_best_view_score();
-    println!("Result (part 2): {result}");
+    fn parse(&self, input: &str) -> Result<Field, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, field: &Field) -> usize {
+        field.get_visible_trees()
+    }
+
+    fn part2(&self, field: &Field) -> i32 {
+        field.get_best_view_score()
+    }
 }
 
-fn parse(input: &Vec<String>) -> Field {
-    let mut lines = vec![];
+fn parse(input: &str) -> Result<Field, ParseError> {
+    let grid = Grid::parse(input, |c| match c.to_digit(10) {
+        Some(height) => Ok(height as i32),
+        None => Err(ParseError::new("Expected a tree height")),
+    })?;
 
-    for line in input {
-        lines.push(
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as i32)
-                .collect(),
-        );
+    if grid.is_empty() {
+        return Err(ParseError::new("Empty map"));
     }
 
-    Field(lines)
+    Ok(Field(grid))
 }
 
 impl Field {
@@ -34,7 +44,7 @@ impl Field {
         let mut current;
 
         let mut trace = |x, y, current: &mut i32| {
-            let value = self.value(x, y).unwrap();
+            let value = self.value(x, y);
             if value > *current {
                 visible.insert((x, y));
                 *current = value;
@@ -74,17 +84,17 @@ impl Field {
         for x in 1..(self.width() - 1) {
             for y in 1..(self.height() - 1) {
                 let mut score = 1;
-                let max = self.value(x, y).unwrap();
+                let max = self.value(x, y);
 
                 for delta in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-                    let (mut dx, mut dy) = (x, y);
+                    let (mut dx, mut dy) = (x as isize, y as isize);
                     let mut distance = 0;
 
                     loop {
-                        dx = (dx as isize + delta.0) as usize;
-                        dy = (dy as isize + delta.1) as usize;
+                        dx += delta.0;
+                        dy += delta.1;
 
-                        match self.value(dx, dy) {
+                        match self.0.get(dx, dy).copied() {
                             None => break,
                             Some(value) => {
                                 distance += 1;
@@ -106,14 +116,38 @@ impl Field {
     }
 
     fn width(&self) -> usize {
-        self.0.get(0).unwrap().len()
+        self.0.width()
     }
 
     fn height(&self) -> usize {
-        self.0.len()
+        self.0.height()
+    }
+
+    fn value(&self, x: usize, y: usize) -> i32 {
+        self.0[(x, y)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+30373
+25512
+65332
+33549
+35390";
+
+    #[test]
+    fn part1_example() {
+        let input = Day8.parse(EXAMPLE).unwrap();
+        assert_eq!(Day8.part1(&input), 21);
     }
 
-    fn value(&self, x: usize, y: usize) -> Option<i32> {
-        self.0.get(y).map(|line| line.get(x).copied()).flatten()
+    #[test]
+    fn part2_example() {
+        let input = Day8.parse(EXAMPLE).unwrap();
+        assert_eq!(Day8.part2(&input), 8);
     }
 }
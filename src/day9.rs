@@ -1,45 +1,48 @@
-use crate::common::get_input_lines;
+use crate::common::Part;
+use crate::error::{parse_lines, parse_token, ParseError};
+use crate::geometry::{Direction, Point2};
+use crate::render::{Canvas, Color, Frames};
+use crate::solution::Solution;
 use std::collections::HashSet;
 use std::str::FromStr;
 
-#[derive(Copy, Clone, Eq, Hash, PartialEq)]
-struct Position(i32, i32);
+type Position = Point2<i32>;
 
-#[derive(Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-struct Move(Direction, u32);
-
-enum Delta {
-    Same,
-    Less,
-    More,
-}
-
-struct RelativePosition(Delta, Delta);
+pub struct Move(Direction, u32);
 
 struct Simulation {
     knots: Vec<Position>,
     visited: HashSet<Position>,
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let input = get_input_lines()
-        .into_iter()
-        .map(|i| i.parse().unwrap())
-        .collect::<Vec<Move>>();
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Move>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Move>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, moves: &Vec<Move>) -> usize {
+        simulate(2, moves)
+    }
+
+    fn part2(&self, moves: &Vec<Move>) -> usize {
+        simulate(10, moves)
+    }
 
-    let result = simulate(2, &input);
-    println!("Result (part 1): {result}");
+    fn render(&self, moves: &Vec<Move>, part: Part, frames: &mut Frames) -> bool {
+        let knots = match part {
+            Part::One => 2,
+            Part::Two => 10,
+        };
 
-    let result = simulate(10, &input);
-    println!("Result (part 2): {result}");
+        render(knots, moves, frames);
+        true
+    }
 }
 
 fn simulate(knots: usize, input: &[Move]) -> usize {
@@ -54,6 +57,32 @@ fn simulate(knots: usize, input: &[Move]) -> usize {
     sim.visited_pos_count()
 }
 
+fn render(knots: usize, input: &[Move], frames: &mut Frames) {
+    // The knots never leave the area covered by the head, so it gives the size of every frame
+    let mut head = Position::default();
+    let (mut min, mut max) = (head, head);
+
+    for m in input {
+        for _ in 0..(m.1) {
+            head = head.step(m.0);
+            min = min.min(head);
+            max = max.max(head);
+        }
+    }
+
+    let mut sim = Simulation::new(knots);
+    frames.push(|| sim.draw(min, max));
+
+    for m in input {
+        for _ in 0..(m.1) {
+            sim.move_head(m.0);
+            frames.push(|| sim.draw(min, max));
+        }
+    }
+
+    frames.finish(|| sim.draw(min, max));
+}
+
 impl Simulation {
     fn new(knots: usize) -> Simulation {
         let mut sim = Simulation {
@@ -70,7 +99,7 @@ impl Simulation {
     }
 
     fn move_head(&mut self, dir: Direction) {
-        self.knots[0] = dir.move_by_one(self.knots[0]);
+        self.knots[0] = self.knots[0].step(dir);
 
         for i in 1..self.knots.len() {
             self.move_knot(i);
@@ -83,80 +112,92 @@ impl Simulation {
         let previous = self.knots[i - 1];
         let knot = self.knots.get_mut(i).unwrap();
 
-        if !knot.touches(previous) {
-            *knot = knot.move_towards(previous);
+        if knot.chebyshev(previous) > 1 {
+            *knot += (previous - *knot).signum();
         }
     }
 
     fn visited_pos_count(&self) -> usize {
         self.visited.len()
     }
-}
 
-impl Position {
-    fn touches(&self, other: Position) -> bool {
-        (self.0 - other.0).abs() <= 1 && (self.1 - other.1).abs() <= 1
-    }
+    /// Draws the positions visited by the tail, the starting point and the knots, from the tail to the head.
+    fn draw(&self, min: Position, max: Position) -> Canvas {
+        let size = max - min;
+        let mut canvas = Canvas::new(size.x as usize + 1, size.y as usize + 1, Color::BLACK);
+        let mut set = |p: Position, color| canvas.set(p.x - min.x, p.y - min.y, color);
 
-    fn get_relative_pos(&self, other: Position) -> RelativePosition {
-        fn delta(d: i32) -> Delta {
-            match d {
-                0 => Delta::Same,
-                1.. => Delta::More,
-                _ => Delta::Less,
-            }
+        for &p in &self.visited {
+            set(p, Color::GRAY);
         }
 
-        RelativePosition(delta(other.0 - self.0), delta(other.1 - self.1))
-    }
-
-    fn move_towards(&self, other: Position) -> Position {
-        self.move_by_one(self.get_relative_pos(other))
-    }
+        set(Position::default(), Color::GREEN);
 
-    fn move_by_one(&self, direction: RelativePosition) -> Position {
-        match direction {
-            RelativePosition(Delta::Same, Delta::Same) => *self,
-            RelativePosition(Delta::Less, Delta::Less) => Position(self.0 - 1, self.1 - 1),
-            RelativePosition(Delta::More, Delta::Less) => Position(self.0 + 1, self.1 - 1),
-            RelativePosition(Delta::Less, Delta::More) => Position(self.0 - 1, self.1 + 1),
-            RelativePosition(Delta::More, Delta::More) => Position(self.0 + 1, self.1 + 1),
-            RelativePosition(Delta::Same, Delta::Less) => Position(self.0, self.1 - 1),
-            RelativePosition(Delta::Same, Delta::More) => Position(self.0, self.1 + 1),
-            RelativePosition(Delta::Less, Delta::Same) => Position(self.0 - 1, self.1),
-            RelativePosition(Delta::More, Delta::Same) => Position(self.0 + 1, self.1),
+        for (i, &knot) in self.knots.iter().enumerate().rev() {
+            set(knot, if i == 0 { Color::RED } else { Color::YELLOW });
         }
-    }
-}
 
-impl Default for Position {
-    fn default() -> Self {
-        Self(0, 0)
-    }
-}
-
-impl Direction {
-    fn move_by_one(&self, p: Position) -> Position {
-        match self {
-            Direction::Up => Position(p.0, p.1 - 1),
-            Direction::Down => Position(p.0, p.1 + 1),
-            Direction::Left => Position(p.0 - 1, p.1),
-            Direction::Right => Position(p.0 + 1, p.1),
-        }
+        let head = self.knots[0] - min;
+        canvas.set_focus(head.x as usize, head.y as usize);
+        canvas
     }
 }
 
 impl FromStr for Move {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let tokens = s.split_whitespace().collect::<Vec<_>>();
         Ok(match tokens[..] {
-            ["U", count] => Move(Direction::Up, count.parse().unwrap()),
-            ["D", count] => Move(Direction::Down, count.parse().unwrap()),
-            ["L", count] => Move(Direction::Left, count.parse().unwrap()),
-            ["R", count] => Move(Direction::Right, count.parse().unwrap()),
-            _ => panic!("Invalid move"),
+            ["U", count] => Move(Direction::Up, parse_token(s, count)?),
+            ["D", count] => Move(Direction::Down, parse_token(s, count)?),
+            ["L", count] => Move(Direction::Left, parse_token(s, count)?),
+            ["R", count] => Move(Direction::Right, parse_token(s, count)?),
+            [direction, _] => return Err(ParseError::at(s, direction, "Invalid direction")),
+            _ => return Err(ParseError::new("Expected a direction and a count")),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+    const LARGER_EXAMPLE: &str = "\
+R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
+    #[test]
+    fn part1_example() {
+        let input = Day9.parse(EXAMPLE).unwrap();
+        assert_eq!(Day9.part1(&input), 13);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day9.parse(EXAMPLE).unwrap();
+        assert_eq!(Day9.part2(&input), 1);
+    }
+
+    #[test]
+    fn part2_larger_example() {
+        let input = Day9.parse(LARGER_EXAMPLE).unwrap();
+        assert_eq!(Day9.part2(&input), 36);
+    }
+}
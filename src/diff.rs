@@ -0,0 +1,112 @@
+use crate::common::{Part, Parts};
+use crate::configure_solution;
+use crate::generate::generate;
+use std::ops::Range;
+
+/// The default size of the generated inputs, which stays small for the reference solutions.
+pub const DEFAULT_DIFF_SIZE: usize = 20;
+
+/// Parameters which keep the reference solutions fast enough, applied before the ones of the generated input.
+const REFERENCE_PARAMS: [(u32, &str, &str); 3] = [
+    (17, "many_rocks", "5000"),
+    (19, "minutes", "18"),
+    (19, "long_minutes", "20"),
+];
+
+/// A generated input on which a solution and its reference disagree.
+pub struct Mismatch {
+    pub seed: u64,
+    pub part: Part,
+    pub answer: String,
+    pub reference: String,
+}
+
+pub struct DiffReport {
+    /// The number of answers which have been compared.
+    pub compared: usize,
+    pub mismatches: Vec<Mismatch>,
+}
+
+/// Compares the answers of a day with the ones of its reference solution, on an input generated for each seed.
+/// The given `(name, value)` pairs override the parameters of the solution. Nothing is compared for the days
+/// without a reference solution.
+pub fn diff(
+    day: u32,
+    seeds: Range<u64>,
+    size: usize,
+    params: &[(String, String)],
+) -> Result<DiffReport, String> {
+    let mut report = DiffReport {
+        compared: 0,
+        mismatches: vec![],
+    };
+
+    for seed in seeds {
+        let generated =
+            generate(day, seed, size).ok_or_else(|| format!("No generator for day {day}"))?;
+
+        let params = REFERENCE_PARAMS
+            .iter()
+            .filter(|&&(reference_day, _, _)| reference_day == day)
+            .map(|&(_, name, value)| (name.to_string(), value.to_string()))
+            .chain(generated.params)
+            .chain(params.iter().cloned())
+            .collect::<Vec<_>>();
+
+        let comparisons = configure_solution(day, &params)?
+            .compare(&generated.input, Parts::Both)
+            .map_err(|e| format!("Invalid input generated with seed {seed}: {e}"))?;
+
+        if comparisons.is_empty() {
+            break;
+        }
+
+        report.compared += comparisons.len();
+
+        report
+            .mismatches
+            .extend(
+                comparisons
+                    .into_iter()
+                    .filter(|i| !i.matches())
+                    .map(|i| Mismatch {
+                        seed,
+                        part: i.part,
+                        answer: i.answer,
+                        reference: i.reference,
+                    }),
+            );
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solutions_match_their_reference() {
+        // Fewer and smaller inputs for day 19, whose reference tries every choice at each minute
+        for (day, seeds, size) in [(15, 10, 10), (16, 10, 10), (17, 10, 10), (19, 4, 4)] {
+            let report = diff(day, 0..seeds, size, &[]).unwrap();
+            assert_eq!(report.compared, 2 * seeds as usize, "Day {day}");
+
+            if let Some(mismatch) = report.mismatches.first() {
+                panic!(
+                    "Day {day}, seed {}, part {}: {}, reference {}",
+                    mismatch.seed,
+                    mismatch.part.number(),
+                    mismatch.answer,
+                    mismatch.reference
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn skips_days_without_reference() {
+        let report = diff(1, 0..10, 10, &[]).unwrap();
+        assert_eq!(report.compared, 0);
+    }
+}
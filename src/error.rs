@@ -0,0 +1,91 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An error in the puzzle input. Lines and columns are 1-based.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error about `token`, which must be a slice of `line`.
+    pub fn at(line: &str, token: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(message).at_column(column_of(line, token))
+    }
+
+    pub fn at_column(mut self, column: usize) -> ParseError {
+        self.column = Some(column);
+        self
+    }
+
+    /// Converts the column of an error found while parsing `token` into a column of `line`,
+    /// where `token` must be a slice of `line`.
+    pub fn within(mut self, line: &str, token: &str) -> ParseError {
+        self.column = Some(column_of(line, token) + self.column.unwrap_or(1) - 1);
+        self
+    }
+
+    /// Sets the line number, unless a more specific one is already known.
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line.get_or_insert(line);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {line}, column {column}: ")?,
+            (Some(line), None) => write!(f, "line {line}: ")?,
+            (None, Some(column)) => write!(f, "column {column}: ")?,
+            (None, None) => {}
+        }
+
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Returns the 1-based column of `token`, which must be a slice of `line`.
+pub fn column_of(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let position = token.as_ptr() as usize;
+
+    if position < start || position > start + line.len() {
+        return 1;
+    }
+
+    line[..position - start].chars().count() + 1
+}
+
+/// Parses `token`, a slice of `line`, reporting its position on failure.
+pub fn parse_token<T: FromStr>(line: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(line, token, format!("Invalid value: '{token}'")))
+}
+
+/// Parses each non-empty line of the input.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| line.parse().map_err(|e: ParseError| e.at_line(index + 1)))
+        .collect()
+}
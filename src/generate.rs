@@ -0,0 +1,1074 @@
+use crate::day24::Day24;
+use crate::interval::IntervalSet;
+use crate::random::Rng;
+use crate::solution::Solution;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// The default size, which gives inputs of about the same scale as the real ones.
+pub const DEFAULT_SIZE: usize = 100;
+
+/// A random puzzle input, along with the parameters it needs to be solved with.
+pub struct Generated {
+    pub input: String,
+    pub params: Vec<(String, String)>,
+}
+
+/// Generates a random input for the given day, which is always the same for a given seed.
+/// The `size` roughly scales the number of items (lines, monkeys, valves...), see [`DEFAULT_SIZE`].
+pub fn generate(day: u32, seed: u64, size: usize) -> Option<Generated> {
+    let rng = &mut Rng::new(seed);
+    let size = size.max(1);
+
+    let input = match day {
+        1 => day1(rng, size),
+        2 => day2(rng, size),
+        3 => day3(rng, size),
+        4 => day4(rng, size),
+        5 => day5(rng, size),
+        6 => day6(rng, size),
+        7 => day7(rng, size),
+        8 => day8(rng, size),
+        9 => day9(rng, size),
+        10 => day10(rng),
+        11 => day11(rng, size),
+        12 => day12(rng, size),
+        13 => day13(rng, size),
+        14 => day14(rng, size),
+        15 => return Some(day15(rng, size)),
+        16 => day16(rng, size),
+        17 => day17(rng, size),
+        18 => day18(rng, size),
+        19 => day19(rng, size),
+        20 => day20(rng, size),
+        21 => day21(rng, size),
+        22 => day22(rng, size),
+        23 => day23(rng, size),
+        24 => day24(rng, size),
+        25 => day25(rng, size),
+        _ => return None,
+    };
+
+    Some(Generated {
+        input,
+        params: vec![],
+    })
+}
+
+/// Returns a random letter, starting from `first` (`b'a'` or `b'A'`).
+fn letter(rng: &mut Rng, first: u8) -> char {
+    (first + rng.index(26) as u8) as char
+}
+
+fn word(rng: &mut Rng, max_len: usize) -> String {
+    (0..=rng.index(max_len))
+        .map(|_| letter(rng, b'a'))
+        .collect()
+}
+
+fn sign(rng: &mut Rng) -> i64 {
+    match rng.chance(0.5) {
+        true => 1,
+        false => -1,
+    }
+}
+
+/// Elves carrying a few calories each.
+fn day1(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for elf in 0..size * 2 {
+        if elf > 0 {
+            out.push('\n');
+        }
+
+        for _ in 0..rng.range(1..=15) {
+            writeln!(out, "{}", rng.range(1000..=60000)).unwrap();
+        }
+    }
+
+    out
+}
+
+fn day2(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for _ in 0..size * 25 {
+        let (a, b) = (rng.index(3) as u8, rng.index(3) as u8);
+        writeln!(out, "{} {}", (b'A' + a) as char, (b'X' + b) as char).unwrap();
+    }
+
+    out
+}
+
+/// Groups of 3 rucksacks using separate letters, apart from their badge,
+/// where the item shared by both compartments is only used once per rucksack.
+fn day3(rng: &mut Rng, size: usize) -> String {
+    let mut letters = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+    let mut out = String::new();
+
+    for _ in 0..size {
+        rng.shuffle(&mut letters);
+        let badge = letters[0];
+
+        for pool in letters[1..].chunks(17) {
+            let (shared, left_pool, right_pool) = (pool[0], &pool[1..9], &pool[9..]);
+            let half = rng.range(2..=16) as usize;
+
+            let mut left = vec![shared, badge];
+            left.extend((2..half).map(|_| *rng.choose(left_pool)));
+            rng.shuffle(&mut left);
+
+            let mut right = vec![shared];
+            right.extend((1..half).map(|_| *rng.choose(right_pool)));
+            rng.shuffle(&mut right);
+
+            out.extend(left.into_iter().chain(right));
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+fn day4(rng: &mut Rng, size: usize) -> String {
+    let mut range = || {
+        let start = rng.range(1..=99);
+        format!("{start}-{}", rng.range(start..=99))
+    };
+
+    let mut out = String::new();
+
+    for _ in 0..size * 10 {
+        writeln!(out, "{},{}", range(), range()).unwrap();
+    }
+
+    out
+}
+
+/// Stacks of crates, and moves which never take more crates than a stack holds.
+fn day5(rng: &mut Rng, size: usize) -> String {
+    let count = rng.range(3..=9) as usize;
+
+    let mut stacks = (0..count)
+        .map(|_| {
+            (0..rng.range(0..=8))
+                .map(|_| letter(rng, b'A'))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    if stacks.iter().all(|i| i.is_empty()) {
+        stacks[0].push(letter(rng, b'A'));
+    }
+
+    let mut out = String::new();
+    let height = stacks.iter().map(|i| i.len()).max().unwrap();
+
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|i| i.get(level).map_or("   ".to_string(), |c| format!("[{c}]")))
+            .collect::<Vec<_>>();
+
+        writeln!(out, "{}", row.join(" ")).unwrap();
+    }
+
+    let labels = (1..=count).map(|i| format!(" {i} ")).collect::<Vec<_>>();
+    writeln!(out, "{}\n", labels.join(" ")).unwrap();
+
+    for _ in 0..size * 5 {
+        let from = loop {
+            match rng.index(count) {
+                i if !stacks[i].is_empty() => break i,
+                _ => {}
+            }
+        };
+
+        let to = loop {
+            match rng.index(count) {
+                i if i != from => break i,
+                _ => {}
+            }
+        };
+
+        let height = stacks[from].len();
+        let moved = rng.range(1..=height as i64) as usize;
+        let crates = stacks[from].split_off(height - moved);
+        stacks[to].extend(crates);
+
+        writeln!(out, "move {moved} from {} to {}", from + 1, to + 1).unwrap();
+    }
+
+    out
+}
+
+/// Repeating letters, followed by a message marker at a random position.
+fn day6(rng: &mut Rng, size: usize) -> String {
+    let len = size * 40 + 14;
+    let mut letters = ('a'..='z').collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+
+    let mut out = (0..rng.index(len - 13))
+        .map(|_| *rng.choose(&letters[14..17]))
+        .collect::<String>();
+
+    out.extend(&letters[..14]);
+
+    while out.len() < len {
+        out.push(letter(rng, b'a'));
+    }
+
+    out.push('\n');
+    out
+}
+
+/// The terminal output of a walk through a random directory tree.
+fn day7(rng: &mut Rng, size: usize) -> String {
+    struct Dir {
+        name: String,
+        subdirs: Vec<usize>,
+        files: Vec<(String, i64)>,
+        names: HashSet<String>,
+    }
+
+    fn list(dirs: &[Dir], index: usize, out: &mut String) {
+        let dir = &dirs[index];
+        writeln!(out, "$ ls").unwrap();
+
+        for &subdir in &dir.subdirs {
+            writeln!(out, "dir {}", dirs[subdir].name).unwrap();
+        }
+
+        for (name, size) in &dir.files {
+            writeln!(out, "{size} {name}").unwrap();
+        }
+
+        for &subdir in &dir.subdirs {
+            writeln!(out, "$ cd {}", dirs[subdir].name).unwrap();
+            list(dirs, subdir, out);
+            writeln!(out, "$ cd ..").unwrap();
+        }
+    }
+
+    let new_dir = |name| Dir {
+        name,
+        subdirs: vec![],
+        files: vec![],
+        names: HashSet::new(),
+    };
+
+    let mut dirs = vec![new_dir("/".to_string())];
+
+    for _ in 0..size * 4 {
+        let parent = rng.index(dirs.len());
+
+        let mut name = word(rng, 8);
+        while dirs[parent].names.contains(&name) {
+            name = word(rng, 8);
+        }
+
+        dirs[parent].names.insert(name.clone());
+
+        if rng.chance(0.25) {
+            dirs.push(new_dir(name));
+            let index = dirs.len() - 1;
+            dirs[parent].subdirs.push(index);
+        } else {
+            let name = match rng.chance(0.5) {
+                true => format!("{name}.{}", word(rng, 3)),
+                false => name,
+            };
+
+            dirs[parent].files.push((name, rng.range(1000..=300_000)));
+        }
+    }
+
+    let mut out = "$ cd /\n".to_string();
+    list(&dirs, 0, &mut out);
+    out
+}
+
+fn day8(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for _ in 0..size {
+        out.extend((0..size).map(|_| (b'0' + rng.index(10) as u8) as char));
+        out.push('\n');
+    }
+
+    out
+}
+
+fn day9(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for _ in 0..size * 20 {
+        let direction = rng.choose(&['U', 'D', 'L', 'R']);
+        writeln!(out, "{direction} {}", rng.range(1..=19)).unwrap();
+    }
+
+    out
+}
+
+/// A program which runs for exactly the 240 cycles drawn on the screen.
+fn day10(rng: &mut Rng) -> String {
+    let mut out = String::new();
+    let mut cycles = 0;
+
+    while cycles < 240 {
+        if cycles <= 238 && rng.chance(0.6) {
+            writeln!(out, "addx {}", rng.range(1..=15) * sign(rng)).unwrap();
+            cycles += 2;
+        } else {
+            writeln!(out, "noop").unwrap();
+            cycles += 1;
+        }
+    }
+
+    out
+}
+
+/// Monkeys testing distinct primes, whose worry levels stay within the range of part 1.
+fn day11(rng: &mut Rng, size: usize) -> String {
+    struct Monkey {
+        items: Vec<i64>,
+        operation: (char, Option<i64>),
+        test: i64,
+        targets: (usize, usize),
+    }
+
+    let count = (size / 12).clamp(2, 8);
+    let mut primes = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+    for attempt in 0.. {
+        rng.shuffle(&mut primes);
+        let squaring = match attempt < 50 && rng.chance(0.5) {
+            true => Some(rng.index(count)),
+            false => None,
+        };
+
+        let monkeys = (0..count)
+            .map(|i| {
+                let operation = match (squaring == Some(i), rng.chance(0.5)) {
+                    (true, _) => ('*', None),
+                    (false, true) => ('*', Some(rng.range(2..=19))),
+                    (false, false) => ('+', Some(rng.range(1..=8))),
+                };
+
+                let mut others = (0..count).filter(|&j| j != i).collect::<Vec<_>>();
+                rng.shuffle(&mut others);
+
+                Monkey {
+                    items: (0..rng.range(1..=6)).map(|_| rng.range(50..=99)).collect(),
+                    operation,
+                    test: primes[i],
+                    targets: (others[0], *others.last().unwrap()),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if !fits_in_part1(&monkeys) {
+            continue;
+        }
+
+        let mut out = String::new();
+
+        for (i, monkey) in monkeys.iter().enumerate() {
+            let items = monkey.items.iter().map(|i| i.to_string());
+            let (operator, operand) = monkey.operation;
+            let operand = operand.map_or("old".to_string(), |i| i.to_string());
+
+            writeln!(out, "Monkey {i}:").unwrap();
+            writeln!(
+                out,
+                "  Starting items: {}",
+                items.collect::<Vec<_>>().join(", ")
+            )
+            .unwrap();
+            writeln!(out, "  Operation: new = old {operator} {operand}").unwrap();
+            writeln!(out, "  Test: divisible by {}", monkey.test).unwrap();
+            writeln!(out, "    If true: throw to monkey {}", monkey.targets.0).unwrap();
+            writeln!(out, "    If false: throw to monkey {}\n", monkey.targets.1).unwrap();
+        }
+
+        return out;
+    }
+
+    /// Plays the 20 rounds of part 1, and checks that the worry levels fit in an i32.
+    fn fits_in_part1(monkeys: &[Monkey]) -> bool {
+        let mut items = monkeys.iter().map(|i| i.items.clone()).collect::<Vec<_>>();
+
+        for _ in 0..20 {
+            for (i, monkey) in monkeys.iter().enumerate() {
+                for item in std::mem::take(&mut items[i]) {
+                    let operand = monkey.operation.1.unwrap_or(item);
+                    let item = match monkey.operation.0 {
+                        '*' => item * operand,
+                        _ => item + operand,
+                    };
+
+                    if item > i32::MAX as i64 {
+                        return false;
+                    }
+
+                    let item = item / 3;
+                    let target = match item % monkey.test {
+                        0 => monkey.targets.0,
+                        _ => monkey.targets.1,
+                    };
+
+                    items[target].push(item);
+                }
+            }
+        }
+
+        true
+    }
+
+    unreachable!()
+}
+
+/// A random landscape, with a winding path climbing from `S` to `E` one step at a time.
+fn day12(rng: &mut Rng, size: usize) -> String {
+    let (width, height) = (size.max(10), (size * 2 / 5).max(8));
+    let length = 26 + rng.index(width * height / 8);
+
+    let neighbors = |rng: &mut Rng, (x, y): (usize, usize)| {
+        let mut result = vec![];
+        result.extend((x > 0).then(|| (x - 1, y)));
+        result.extend((y > 0).then(|| (x, y - 1)));
+        result.extend((x + 1 < width).then_some((x + 1, y)));
+        result.extend((y + 1 < height).then_some((x, y + 1)));
+        rng.shuffle(&mut result);
+        result
+    };
+
+    // A random depth-first walk, which backtracks from dead ends until the path is long enough
+    let path = loop {
+        let start = (rng.index(width), rng.index(height));
+        let mut visited = vec![vec![false; width]; height];
+        visited[start.1][start.0] = true;
+
+        let mut path = vec![start];
+        let mut options = vec![neighbors(rng, start)];
+
+        while !path.is_empty() && path.len() < length {
+            match options.last_mut().unwrap().pop() {
+                Some((x, y)) if !visited[y][x] => {
+                    visited[y][x] = true;
+                    path.push((x, y));
+                    options.push(neighbors(rng, (x, y)));
+                }
+                Some(_) => {}
+                None => {
+                    path.pop();
+                    options.pop();
+                }
+            }
+        }
+
+        if path.len() == length {
+            break path;
+        }
+    };
+
+    let mut grid = (0..height)
+        .map(|_| (0..width).map(|_| letter(rng, b'a')).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    for (i, &(x, y)) in path.iter().enumerate() {
+        grid[y][x] = (b'a' + (25 * i / (length - 1)) as u8) as char;
+    }
+
+    let ((start_x, start_y), (end_x, end_y)) = (path[0], path[length - 1]);
+    grid[start_y][start_x] = 'S';
+    grid[end_y][end_x] = 'E';
+
+    grid.into_iter()
+        .map(|row| row.into_iter().chain(['\n']).collect::<String>())
+        .collect()
+}
+
+fn day13(rng: &mut Rng, size: usize) -> String {
+    fn packet(rng: &mut Rng, depth: usize) -> String {
+        let items = (0..rng.index(5))
+            .map(|_| match depth == 0 || rng.chance(0.6) {
+                true => rng.index(11).to_string(),
+                false => packet(rng, depth - 1),
+            })
+            .collect::<Vec<_>>();
+
+        format!("[{}]", items.join(","))
+    }
+
+    let pairs = (0..size)
+        .map(|_| format!("{}\n{}\n", packet(rng, 4), packet(rng, 4)))
+        .collect::<Vec<_>>();
+
+    pairs.join("\n")
+}
+
+/// Rock paths of alternating horizontal and vertical segments, below the sand source.
+fn day14(rng: &mut Rng, size: usize) -> String {
+    let spread = size as i64 / 2 + 10;
+    let mut out = String::new();
+
+    for _ in 0..size {
+        let (mut x, mut y) = (
+            500 + rng.range(-spread..=spread),
+            rng.range(spread / 4..=spread),
+        );
+        let mut points = vec![format!("{x},{y}")];
+        let horizontal = rng.chance(0.5);
+
+        for i in 0..rng.range(1..=4) {
+            let len = rng.range(1..=8);
+
+            if (i % 2 == 0) == horizontal {
+                x += len * sign(rng);
+            } else if y > len && rng.chance(0.5) {
+                y -= len;
+            } else {
+                y += len;
+            }
+
+            points.push(format!("{x},{y}"));
+        }
+
+        writeln!(out, "{}", points.join(" -> ")).unwrap();
+    }
+
+    out
+}
+
+/// Sensors covering the whole search space apart from the distress beacon, which is surrounded by 4 of them.
+/// The remaining gaps are then filled row by row, with sensors reaching up to the distress beacon.
+fn day15(rng: &mut Rng, size: usize) -> Generated {
+    let space = (size as i64 * 10).max(20);
+    let distress = (rng.range(0..=space), rng.range(0..=space));
+    let distance = |(x1, y1): (i64, i64), (x2, y2): (i64, i64)| (x1 - x2).abs() + (y1 - y2).abs();
+
+    let report = |rng: &mut Rng, sensor: (i64, i64), radius: i64| {
+        let dx = rng.range(-radius..=radius);
+        let dy = (radius - dx.abs()) * sign(rng);
+        (sensor, (sensor.0 + dx, sensor.1 + dy))
+    };
+
+    let mut reports = vec![];
+
+    let offset = rng.range(space / 8 + 1..=space / 4 + 1);
+
+    for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+        let sensor = (distress.0 + dx * offset, distress.1 + dy * offset);
+        reports.push(report(rng, sensor, 2 * offset - 1));
+    }
+
+    for y in 0..=space {
+        loop {
+            let covered = reports
+                .iter()
+                .filter_map(|&(sensor, beacon)| {
+                    let width = distance(sensor, beacon) - (sensor.1 - y).abs();
+                    (width >= 0).then(|| sensor.0 - width..=sensor.0 + width)
+                })
+                .collect::<IntervalSet<_>>();
+
+            let mut uncovered = covered.complement(0..=space);
+
+            if y == distress.1 {
+                uncovered.remove(distress.0..=distress.0);
+            }
+
+            let Some(x) = uncovered.first() else {
+                break;
+            };
+
+            reports.push(report(rng, (x, y), distance((x, y), distress) - 1));
+        }
+    }
+
+    rng.shuffle(&mut reports);
+    let mut input = String::new();
+
+    for ((sx, sy), (bx, by)) in reports {
+        writeln!(
+            input,
+            "Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}"
+        )
+        .unwrap();
+    }
+
+    Generated {
+        input,
+        params: vec![
+            ("row".to_string(), (space / 2).to_string()),
+            ("search_space".to_string(), space.to_string()),
+        ],
+    }
+}
+
+/// A connected network of valves, with a few of them releasing pressure.
+fn day16(rng: &mut Rng, size: usize) -> String {
+    let count = (size * 3 / 5).clamp(2, 26 * 26);
+    let flowing = (count / 4).clamp(1, 15);
+
+    let mut names = (b'A'..=b'Z')
+        .flat_map(|a| (b'A'..=b'Z').map(move |b| format!("{}{}", a as char, b as char)))
+        .filter(|i| i != "AA")
+        .collect::<Vec<_>>();
+
+    rng.shuffle(&mut names);
+    names.truncate(count - 1);
+    names.insert(0, "AA".to_string());
+
+    let mut tunnels = vec![vec![]; count];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+
+    for i in 1..count {
+        connect(i, rng.index(i));
+    }
+
+    for _ in 0..count / 3 {
+        connect(rng.index(count), rng.index(count));
+    }
+
+    let mut flows = vec![0; count];
+    let mut valves = (1..count).collect::<Vec<_>>();
+    rng.shuffle(&mut valves);
+
+    for &valve in &valves[..flowing] {
+        flows[valve] = rng.range(1..=25);
+    }
+
+    valves.push(0);
+    rng.shuffle(&mut valves);
+    let mut out = String::new();
+
+    for valve in valves {
+        let targets = tunnels[valve]
+            .iter()
+            .map(|&i| names[i].as_str())
+            .collect::<Vec<_>>();
+
+        let tunnels = match targets.len() {
+            1 => "tunnel leads to valve",
+            _ => "tunnels lead to valves",
+        };
+
+        writeln!(
+            out,
+            "Valve {} has flow rate={}; {tunnels} {}",
+            names[valve],
+            flows[valve],
+            targets.join(", ")
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+fn day17(rng: &mut Rng, size: usize) -> String {
+    let mut out = (0..size * 100)
+        .map(|_| *rng.choose(&['<', '>']))
+        .collect::<String>();
+
+    out.push('\n');
+    out
+}
+
+fn day18(rng: &mut Rng, size: usize) -> String {
+    let count = size * 20;
+    let side = ((count * 3) as f64).cbrt().ceil() as i64 + 1;
+    let mut cubes = HashSet::new();
+    let mut out = String::new();
+
+    while cubes.len() < count {
+        let cube = (
+            rng.range(1..=side),
+            rng.range(1..=side),
+            rng.range(1..=side),
+        );
+
+        if cubes.insert(cube) {
+            writeln!(out, "{},{},{}", cube.0, cube.1, cube.2).unwrap();
+        }
+    }
+
+    out
+}
+
+/// Blueprints like the puzzle ones, where the clay and obsidian get cheaper in small inputs,
+/// so that some geodes are still opened in the short times given to the reference solution.
+fn day19(rng: &mut Rng, size: usize) -> String {
+    let max_cost = (size as i64).clamp(5, 20);
+    let mut out = String::new();
+
+    for id in 1..=(size * 3 / 10).max(1) {
+        writeln!(
+            out,
+            "Blueprint {id}: \
+             Each ore robot costs {} ore. \
+             Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. \
+             Each geode robot costs {} ore and {} obsidian.",
+            rng.range(2..=4),
+            rng.range(2..=4),
+            rng.range(2..=4),
+            rng.range(5..=max_cost),
+            rng.range(2..=4),
+            rng.range(5..=max_cost),
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+/// Numbers which may repeat, apart from the single 0.
+fn day20(rng: &mut Rng, size: usize) -> String {
+    let mut numbers = (0..size * 50)
+        .map(|_| rng.range(1..=10000) * sign(rng))
+        .collect::<Vec<_>>();
+
+    numbers.insert(rng.index(numbers.len() + 1), 0);
+
+    numbers.into_iter().map(|i| format!("{i}\n")).collect()
+}
+
+/// A chain of operations leading from `humn` up to `root`, where each one can be reversed exactly,
+/// so that the value shouted by `humn` is also the answer of part 2.
+fn day21(rng: &mut Rng, size: usize) -> String {
+    const LIMIT: i64 = 1_000_000_000_000;
+
+    struct Monkeys<'a> {
+        rng: &'a mut Rng,
+        names: HashSet<String>,
+        lines: Vec<String>,
+    }
+
+    impl Monkeys<'_> {
+        fn add(&mut self, job: String) -> String {
+            let mut name = self.name();
+            while !self.names.insert(name.clone()) {
+                name = self.name();
+            }
+
+            self.lines.push(format!("{name}: {job}"));
+            name
+        }
+
+        fn name(&mut self) -> String {
+            (0..4).map(|_| letter(self.rng, b'a')).collect()
+        }
+
+        /// Adds a random tree of monkeys, and returns the name and value of its root.
+        fn tree(&mut self, depth: usize) -> (String, i64) {
+            if depth == 0 || self.rng.chance(0.3) {
+                let value = self.rng.range(1..=20);
+                return (self.add(value.to_string()), value);
+            }
+
+            let (a, value_a) = self.tree(depth - 1);
+            let (b, value_b) = self.tree(depth - 1);
+            let (op, value) = self.operation(value_a, value_b, |op, a, b| match op {
+                '+' => Some(a + b),
+                '-' => Some(a - b),
+                '*' => a.checked_mul(b).filter(|i| i.abs() <= LIMIT),
+                _ => (b != 0 && a % b == 0).then(|| a / b),
+            });
+
+            (self.add(format!("{a} {op} {b}")), value)
+        }
+
+        /// Picks a random operation which is valid for the given operands, addition being always valid.
+        fn operation(
+            &mut self,
+            a: i64,
+            b: i64,
+            apply: impl Fn(char, i64, i64) -> Option<i64>,
+        ) -> (char, i64) {
+            let mut ops = ['+', '-', '*', '/'];
+            self.rng.shuffle(&mut ops);
+
+            ops.into_iter()
+                .find_map(|op| apply(op, a, b).map(|value| (op, value)))
+                .unwrap()
+        }
+    }
+
+    let mut monkeys = Monkeys {
+        rng,
+        names: HashSet::from(["root".to_string(), "humn".to_string()]),
+        lines: vec![],
+    };
+
+    let humn = monkeys.rng.range(1..=5000);
+    monkeys.lines.push(format!("humn: {humn}"));
+    let (mut name, mut value) = ("humn".to_string(), humn);
+
+    for _ in 0..(size * 7 / 10).max(1) {
+        let depth = monkeys.rng.index(4);
+        let (other, other_value) = monkeys.tree(depth);
+        let humn_first = monkeys.rng.chance(0.5);
+
+        // Multiplying by 0, or dividing into 0, would lose the value of humn
+        let (op, next) = monkeys.operation(value, other_value, |op, h, v| match op {
+            '+' => Some(h + v),
+            '-' if humn_first => Some(h - v),
+            '-' => Some(v - h),
+            '*' => h.checked_mul(v).filter(|i| v != 0 && i.abs() <= LIMIT),
+            _ if humn_first => (v != 0 && h % v == 0).then(|| h / v),
+            _ => (v != 0 && v % h == 0).then(|| v / h),
+        });
+
+        name = match humn_first {
+            true => monkeys.add(format!("{name} {op} {other}")),
+            false => monkeys.add(format!("{other} {op} {name}")),
+        };
+
+        value = next;
+    }
+
+    // The other side of root needs to match the value computed from humn
+    let depth = monkeys.rng.index(4);
+    let (mut other, other_value) = monkeys.tree(depth);
+
+    if other_value != value {
+        let (op, difference) = match other_value < value {
+            true => ('+', value - other_value),
+            false => ('-', other_value - value),
+        };
+
+        let constant = monkeys.add(difference.to_string());
+        other = monkeys.add(format!("{other} {op} {constant}"));
+    }
+
+    let root = match monkeys.rng.chance(0.5) {
+        true => format!("root: {name} + {other}"),
+        false => format!("root: {other} + {name}"),
+    };
+
+    monkeys.lines.push(root);
+    monkeys.rng.shuffle(&mut monkeys.lines);
+
+    monkeys.lines.iter().map(|i| format!("{i}\n")).collect()
+}
+
+/// The 11 nets of a cube, where each `#` is a face.
+pub(crate) const CUBE_NETS: [&str; 11] = [
+    "#...\n####\n#...",
+    ".#..\n####\n#...",
+    "..#.\n####\n#...",
+    "...#\n####\n#...",
+    ".#..\n####\n.#..",
+    ".#..\n####\n..#.",
+    "##..\n.###\n.#..",
+    "##..\n.###\n..#.",
+    "##..\n.###\n...#",
+    "##..\n.##.\n..##",
+    "###..\n..###",
+];
+
+/// A board folding into a cube, using a random net which is rotated and flipped, and a path along it.
+fn day22(rng: &mut Rng, size: usize) -> String {
+    let face_size = (size / 2).clamp(2, 100);
+
+    let mut faces = rng
+        .choose(&CUBE_NETS)
+        .lines()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.match_indices('#')
+                .map(move |(x, _)| (x as i64, y as i64))
+        })
+        .collect::<Vec<_>>();
+
+    let flip = rng.chance(0.5);
+
+    for _ in 0..rng.index(4) {
+        for face in faces.iter_mut() {
+            *face = (-face.1, face.0);
+        }
+    }
+
+    for face in faces.iter_mut() {
+        if flip {
+            face.0 = -face.0;
+        }
+    }
+
+    let min_x = faces.iter().map(|i| i.0).min().unwrap();
+    let min_y = faces.iter().map(|i| i.1).min().unwrap();
+    let width = faces.iter().map(|i| i.0 - min_x + 1).max().unwrap() as usize;
+    let height = faces.iter().map(|i| i.1 - min_y + 1).max().unwrap() as usize;
+
+    let mut board = vec![vec![' '; width * face_size]; height * face_size];
+
+    for &(x, y) in &faces {
+        let (x, y) = (
+            (x - min_x) as usize * face_size,
+            (y - min_y) as usize * face_size,
+        );
+
+        for row in &mut board[y..y + face_size] {
+            for tile in &mut row[x..x + face_size] {
+                *tile = match rng.chance(0.15) {
+                    true => '#',
+                    false => '.',
+                };
+            }
+        }
+    }
+
+    // The path starts on the leftmost tile of the top row, which needs to be open
+    let start = board[0].iter().position(|&i| i != ' ').unwrap();
+    board[0][start] = '.';
+
+    let mut out = String::new();
+
+    for row in board {
+        writeln!(out, "{}", row.into_iter().collect::<String>().trim_end()).unwrap();
+    }
+
+    out.push('\n');
+
+    for i in 0..size * 20 {
+        if i > 0 {
+            out.push(*rng.choose(&['L', 'R']));
+        }
+
+        write!(out, "{}", rng.range(1..=2 * face_size as i64)).unwrap();
+    }
+
+    out.push('\n');
+    out
+}
+
+/// A square patch of ground, about half covered with elves.
+fn day23(rng: &mut Rng, size: usize) -> String {
+    let side = (size * 7 / 10).max(3);
+    let mut out = String::new();
+
+    for y in 0..side {
+        for x in 0..side {
+            // At least one elf, so that there is a rectangle around them
+            let elf = (x, y) == (side / 2, side / 2) || rng.chance(0.5);
+            out.push(if elf { '#' } else { '.' });
+        }
+
+        out.push('\n');
+    }
+
+    out
+}
+
+/// A valley full of blizzards, with the entrance at the top left and the exit at the bottom right.
+/// No blizzard goes up or down in their columns, where it would leave the valley.
+fn day24(rng: &mut Rng, size: usize) -> String {
+    // Small valleys are often blocked, so they are generated until the expedition can go through
+    loop {
+        let out = day24_valley(rng, size);
+        let valley = Day24.parse(&out).unwrap();
+
+        if valley.can_travel() {
+            return out;
+        }
+    }
+}
+
+fn day24_valley(rng: &mut Rng, size: usize) -> String {
+    let width = (size * 6 / 5).max(3);
+    let height = (size / 4).max(3);
+    let mut out = String::new();
+
+    writeln!(out, "#.{}", "#".repeat(width)).unwrap();
+
+    for _ in 0..height {
+        out.push('#');
+
+        for x in 0..width {
+            let vertical = x > 0 && x < width - 1;
+
+            out.push(match rng.index(8) {
+                0 => '>',
+                1 => '<',
+                2 if vertical => '^',
+                3 if vertical => 'v',
+                _ => '.',
+            });
+        }
+
+        out.push_str("#\n");
+    }
+
+    writeln!(out, "{}.#", "#".repeat(width)).unwrap();
+    out
+}
+
+/// Positive SNAFU numbers of up to 20 digits, which start with `1` or `2`.
+fn day25(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for _ in 0..size.max(1) {
+        out.push(*rng.choose(&['1', '2']));
+
+        for _ in 0..rng.index(20) {
+            out.push(*rng.choose(&['=', '-', '0', '1', '2']));
+        }
+
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Parts;
+    use crate::{configure_solution, DAYS};
+
+    #[test]
+    fn solves_generated_inputs() {
+        for day in DAYS {
+            for seed in 0..5 {
+                let generated = generate(day, seed, 5).unwrap();
+                let solution = configure_solution(day, &generated.params).unwrap();
+
+                let result = solution.run(&generated.input, Parts::Both);
+                assert!(result.is_ok(), "Day {day}, seed {seed}: {:?}", result.err());
+            }
+        }
+    }
+
+    #[test]
+    fn is_reproducible() {
+        for day in DAYS {
+            let input = |seed| generate(day, seed, 10).unwrap().input;
+            assert_eq!(input(1), input(1));
+            assert_ne!(input(1), input(2));
+        }
+    }
+
+    #[test]
+    fn day21_answer_is_humn() {
+        for seed in 0..10 {
+            let input = generate(21, seed, 20).unwrap().input;
+            let humn = input.lines().find_map(|i| i.strip_prefix("humn: "));
+
+            let result = configure_solution(21, &[])
+                .unwrap()
+                .run(&input, Parts::Both)
+                .unwrap();
+
+            assert_eq!(result.parts[1].answer.as_deref().ok(), humn);
+        }
+    }
+}
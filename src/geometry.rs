@@ -0,0 +1,349 @@
+use crate::error::{parse_token, ParseError};
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// The integer types which can be used as coordinates.
+pub trait Number:
+    Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+pub trait Signed: Number + Neg<Output = Self> {
+    fn signum(self) -> Self {
+        match self.cmp(&Self::ZERO) {
+            std::cmp::Ordering::Less => -Self::ONE,
+            std::cmp::Ordering::Equal => Self::ZERO,
+            std::cmp::Ordering::Greater => Self::ONE,
+        }
+    }
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {
+        $(impl Number for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })*
+    };
+}
+
+impl_number!(i32, i64, isize, u32, u64, usize);
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for isize {}
+
+/// A point in 2D space, with `y` going down.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Debug)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point in 3D space.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Debug)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Number> Point2<T> {
+    pub fn manhattan(self, other: Point2<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Point2<T>) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Returns the adjacent point in the given direction.
+    pub fn step(self, direction: Direction) -> Point2<T> {
+        match direction {
+            Direction::Up => Point2::new(self.x, self.y - T::ONE),
+            Direction::Down => Point2::new(self.x, self.y + T::ONE),
+            Direction::Left => Point2::new(self.x - T::ONE, self.y),
+            Direction::Right => Point2::new(self.x + T::ONE, self.y),
+        }
+    }
+
+    pub fn min(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    pub fn max(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x.max(other.x), self.y.max(other.y))
+    }
+}
+
+impl<T: Signed> Point2<T> {
+    /// Returns the point with each coordinate replaced by its sign, which is a step towards it from the origin.
+    pub fn signum(self) -> Point2<T> {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Number> Point3<T> {
+    pub fn manhattan(self, other: Point3<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(self, other: Point3<T>) -> T {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    pub fn min(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    pub fn max(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    /// Returns the 6 points which share a face with this one.
+    pub fn neighbors6(self) -> [Point3<T>; 6] {
+        let one = T::ONE;
+
+        [
+            Point3::new(self.x, self.y - one, self.z),
+            Point3::new(self.x, self.y + one, self.z),
+            Point3::new(self.x - one, self.y, self.z),
+            Point3::new(self.x + one, self.y, self.z),
+            Point3::new(self.x, self.y, self.z - one),
+            Point3::new(self.x, self.y, self.z + one),
+        ]
+    }
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// Turns clockwise.
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    /// Turns counterclockwise.
+    pub fn turn_left(self) -> Direction {
+        self.turn_right().opposite()
+    }
+
+    pub fn turn(self, turn: Turn) -> Direction {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+
+    pub fn delta<T: Signed>(self) -> Point2<T> {
+        Point2::new(T::ZERO, T::ZERO).step(self)
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($field:ident),* }) => {
+        impl<T: Number> Add for $point<T> {
+            type Output = $point<T>;
+
+            fn add(self, other: $point<T>) -> $point<T> {
+                $point { $($field: self.$field + other.$field),* }
+            }
+        }
+
+        impl<T: Number> Sub for $point<T> {
+            type Output = $point<T>;
+
+            fn sub(self, other: $point<T>) -> $point<T> {
+                $point { $($field: self.$field - other.$field),* }
+            }
+        }
+
+        impl<T: Number> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: $point<T>) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Number> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: $point<T>) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Number> Mul<T> for $point<T> {
+            type Output = $point<T>;
+
+            fn mul(self, factor: T) -> $point<T> {
+                $point { $($field: self.$field * factor),* }
+            }
+        }
+
+        impl<T: Signed> Neg for $point<T> {
+            type Output = $point<T>;
+
+            fn neg(self) -> $point<T> {
+                $point { $($field: -self.$field),* }
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// Parses comma-separated coordinates, such as `498,4`.
+impl<T: FromStr> FromStr for Point2<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_coordinates(s)[..] {
+            [x, y] => Ok(Point2::new(parse_token(s, x)?, parse_token(s, y)?)),
+            _ => Err(ParseError::new("Expected a point, such as 498,4")),
+        }
+    }
+}
+
+/// Parses comma-separated coordinates, such as `2,2,2`.
+impl<T: FromStr> FromStr for Point3<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_coordinates(s)[..] {
+            [x, y, z] => Ok(Point3::new(
+                parse_token(s, x)?,
+                parse_token(s, y)?,
+                parse_token(s, z)?,
+            )),
+            _ => Err(ParseError::new("Expected a point, such as 2,2,2")),
+        }
+    }
+}
+
+fn parse_coordinates(s: &str) -> Vec<&str> {
+    s.split(',').map(|i| i.trim()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(-3, 4);
+
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(Point3::new(1u32, 2, 3).manhattan(Point3::new(3, 2, 1)), 4);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(-3, 4);
+
+        assert_eq!(a + b, Point2::new(-2, 2));
+        assert_eq!(a - b, Point2::new(4, -6));
+        assert_eq!(-a * 2, Point2::new(-2, 4));
+        assert_eq!((b - a).signum(), Point2::new(-1, 1));
+    }
+
+    #[test]
+    fn directions() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(direction.delta::<i32>(), -direction.opposite().delta());
+        }
+
+        assert_eq!(Direction::Up.delta(), Point2::new(0, -1));
+        assert_eq!(Direction::Up.turn(Turn::Right), Direction::Right);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("498,4".parse(), Ok(Point2::new(498, 4)));
+        assert_eq!("2, 2, 5".parse(), Ok(Point3::new(2, 2, 5)));
+
+        let error = "1,x".parse::<Point2<i32>>().unwrap_err();
+        assert_eq!(error.column, Some(3));
+    }
+}
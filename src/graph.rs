@@ -0,0 +1,298 @@
+use crate::geometry::Number;
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+/// A path found by a search, from the start node to the goal, both included.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// The error of the puzzles whose answer is a path, when the goal cannot be reached.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct NoPath;
+
+/// The nodes reached by a search, along with their cost and the node they were reached from.
+pub struct Paths<N, C> {
+    visited: HashMap<N, (C, Option<N>)>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Paths<N, C> {
+    fn new(start: N, zero: C) -> Paths<N, C> {
+        Paths {
+            visited: HashMap::from([(start, (zero, None))]),
+        }
+    }
+
+    /// Returns the cost of the shortest path to `node`, or `None` when it could not be reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.visited.get(node).map(|&(cost, _)| cost)
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.visited.contains_key(node)
+    }
+
+    pub fn len(&self) -> usize {
+        self.visited.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visited.is_empty()
+    }
+
+    /// Iterates over the reached nodes and their cost, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> + '_ {
+        self.visited.iter().map(|(node, &(cost, _))| (node, cost))
+    }
+
+    /// Reconstructs the shortest path to `node`.
+    pub fn path_to(&self, node: &N) -> Option<Path<N, C>> {
+        let cost = self.cost(node)?;
+        let mut nodes = vec![node.clone()];
+
+        while let Some((_, Some(parent))) = self.visited.get(nodes.last().unwrap()) {
+            nodes.push(parent.clone());
+        }
+
+        nodes.reverse();
+        Some(Path { nodes, cost })
+    }
+}
+
+impl Display for NoPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No path found")
+    }
+}
+
+/// Visits every node reachable from `start`, where each edge has a cost of 1.
+pub fn bfs<N, I>(start: N, neighbors: impl FnMut(&N) -> I) -> Paths<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    breadth_first(start, neighbors, |_| false).0
+}
+
+/// Returns the shortest path from `start` to the first node matching `goal`, where each edge has a cost of 1.
+pub fn bfs_to<N, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let (paths, found) = breadth_first(start, neighbors, goal);
+    paths.path_to(&found?)
+}
+
+/// Visits every node reachable from `start`. The neighbors are returned along with the cost of the edge leading to them.
+pub fn dijkstra<N, C, I>(start: N, neighbors: impl FnMut(&N) -> I) -> Paths<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Number,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(start, neighbors, |_| C::ZERO, |_| false).0
+}
+
+/// Returns the cheapest path from `start` to the first node matching `goal`.
+pub fn dijkstra_to<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Number,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (paths, found) = best_first(start, neighbors, |_| C::ZERO, goal);
+    paths.path_to(&found?)
+}
+
+/// Returns the cheapest path from `start` to the first node matching `goal`, exploring the nodes which look closest
+/// to it first. The `heuristic` must never overestimate the remaining cost, or the path may not be the cheapest one.
+pub fn astar<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Number,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (paths, found) = best_first(start, neighbors, heuristic, goal);
+    paths.path_to(&found?)
+}
+
+fn breadth_first<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Paths<N, usize>, Option<N>)
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new(start.clone(), 0);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, cost)) = queue.pop_front() {
+        if goal(&node) {
+            return (paths, Some(node));
+        }
+
+        for next in neighbors(&node) {
+            if let Entry::Vacant(entry) = paths.visited.entry(next.clone()) {
+                entry.insert((cost + 1, Some(node.clone())));
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    (paths, None)
+}
+
+/// Dijkstra's algorithm, which turns into A* when given a heuristic.
+fn best_first<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Paths<N, C>, Option<N>)
+where
+    N: Eq + Hash + Clone,
+    C: Number,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new(start.clone(), C::ZERO);
+    let mut queue = BinaryHeap::from([QueueEntry {
+        priority: heuristic(&start),
+        cost: C::ZERO,
+        node: start,
+    }]);
+
+    while let Some(QueueEntry { cost, node, .. }) = queue.pop() {
+        // The node may have been queued again with a lower cost since this entry was added
+        if cost > paths.visited[&node].0 {
+            continue;
+        }
+
+        if goal(&node) {
+            return (paths, Some(node));
+        }
+
+        for (next, edge_cost) in neighbors(&node) {
+            let next_cost = cost + edge_cost;
+
+            if paths.cost(&next).is_some_and(|known| known <= next_cost) {
+                continue;
+            }
+
+            paths
+                .visited
+                .insert(next.clone(), (next_cost, Some(node.clone())));
+
+            queue.push(QueueEntry {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+
+    (paths, None)
+}
+
+/// An entry of the priority queue, ordered so that the lowest priority is popped first from the max-heap.
+struct QueueEntry<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for QueueEntry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for QueueEntry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for QueueEntry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for QueueEntry<N, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A weighted graph with a cheap detour: 0 -> 1 -> 2 -> 3 costs 3, while 0 -> 3 costs 5.
+    fn weighted(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 1), (3, 5)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        let paths = bfs(0, |&i: &i32| {
+            [i - 1, i + 1].into_iter().filter(|i| i.abs() <= 3)
+        });
+
+        assert_eq!(paths.len(), 7);
+        assert_eq!(paths.cost(&-3), Some(3));
+        assert_eq!(paths.cost(&4), None);
+        assert_eq!(paths.path_to(&2).unwrap().nodes, vec![0, 1, 2]);
+
+        let path = bfs_to(1, |&i: &i32| [i + 1, i * 2], |&i| i == 10).unwrap();
+        assert_eq!(path.nodes, vec![1, 2, 4, 5, 10]);
+    }
+
+    #[test]
+    fn dijkstra_takes_the_cheapest_path() {
+        let path = dijkstra_to(0, weighted, |&i| i == 3).unwrap();
+        assert_eq!(path.nodes, vec![0, 1, 2, 3]);
+        assert_eq!(path.cost, 3);
+
+        let paths = dijkstra(0, weighted);
+        assert_eq!(paths.cost(&2), Some(2));
+        assert_eq!(paths.cost(&4), None);
+    }
+
+    #[test]
+    fn astar_on_a_line() {
+        let target = 20i64;
+        let path = astar(
+            0,
+            |&i: &i64| [(i - 1, 1), (i + 1, 1)],
+            |&i| (i - target).abs(),
+            |&i| i == target,
+        )
+        .unwrap();
+
+        assert_eq!(path.cost, 20);
+        assert_eq!(path.nodes.len(), 21);
+    }
+}
@@ -0,0 +1,346 @@
+use crate::error::ParseError;
+use crate::geometry::Point2;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// A rectangular 2D board, stored row by row. Positions are `(x, y)` pairs or [`Point2`], with `y` going down.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: (0..width * height)
+                .map(|i| f(i % width, i / width))
+                .collect(),
+        }
+    }
+
+    /// Parses a character map, where each non-empty line is a row. All the rows must have the same width.
+    /// Errors returned by `f` are reported at the position of the character.
+    pub fn parse(
+        input: &str,
+        f: impl FnMut(char) -> Result<T, ParseError>,
+    ) -> Result<Grid<T>, ParseError> {
+        let rows = parse_rows(input, f)?;
+        let width = rows.first().map_or(0, |(_, row)| row.len());
+
+        if let Some((index, _)) = rows.iter().find(|(_, row)| row.len() != width) {
+            return Err(ParseError::new("Invalid line width").at_line(index + 1));
+        }
+
+        Ok(Grid::from_rows(rows.into_iter().map(|(_, row)| row), width))
+    }
+
+    /// Parses a character map like [`Grid::parse`], but pads the rows which are too short with `fill`.
+    pub fn parse_ragged(
+        input: &str,
+        fill: T,
+        f: impl FnMut(char) -> Result<T, ParseError>,
+    ) -> Result<Grid<T>, ParseError>
+    where
+        T: Clone,
+    {
+        let rows = parse_rows(input, f)?;
+        let width = rows.iter().map(|(_, row)| row.len()).max().unwrap_or(0);
+
+        let rows = rows.into_iter().map(|(_, mut row)| {
+            row.resize(width, fill.clone());
+            row
+        });
+
+        Ok(Grid::from_rows(rows, width))
+    }
+
+    fn from_rows(rows: impl Iterator<Item = Vec<T>>, width: usize) -> Grid<T> {
+        let cells = rows.flatten().collect::<Vec<_>>();
+
+        Grid {
+            width,
+            height: cells.len().checked_div(width).unwrap_or(0),
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the cell at the given position, or `None` when it lies outside the grid.
+    /// Coordinates may be signed, so that neighbors can be looked up without checking for underflow.
+    pub fn get<I: TryInto<usize>>(&self, x: I, y: I) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut<I: TryInto<usize>>(&mut self, x: I, y: I) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// Returns the cell at the given point, like [`Grid::get`].
+    pub fn get_at<I: TryInto<usize>>(&self, point: Point2<I>) -> Option<&T> {
+        self.get(point.x, point.y)
+    }
+
+    pub fn get_at_mut<I: TryInto<usize>>(&mut self, point: Point2<I>) -> Option<&mut T> {
+        self.get_mut(point.x, point.y)
+    }
+
+    pub fn contains<I: TryInto<usize>>(&self, x: I, y: I) -> bool {
+        self.index_of(x, y).is_some()
+    }
+
+    fn index_of<I: TryInto<usize>>(&self, x: I, y: I) -> Option<usize> {
+        let (x, y) = (x.try_into().ok()?, y.try_into().ok()?);
+
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the positions of the cells above, below, left and right of the given one.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, &[(0, -1), (0, 1), (-1, 0), (1, 0)])
+    }
+
+    /// Returns the points of the cells above, below, left and right of the given one.
+    pub fn neighbors4_at(&self, point: Point2<usize>) -> impl Iterator<Item = Point2<usize>> + '_ {
+        self.neighbors4(point.x, point.y)
+            .map(|(x, y)| Point2::new(x, y))
+    }
+
+    /// Returns the positions of the cells around the given one, including the diagonal ones.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        const DELTAS: [(isize, isize); 8] = [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ];
+
+        self.neighbors(x, y, &DELTAS)
+    }
+
+    fn neighbors(
+        &self,
+        x: usize,
+        y: usize,
+        deltas: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        deltas.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            self.index_of(x, y).map(|_| (x, y))
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width, "Column out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// Iterates over the cells along with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Returns the point of the first cell, row by row, which matches the predicate.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point2<usize>> {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|((x, y), _)| Point2::new(x, y))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "Position out of bounds");
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "Position out of bounds");
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Indexing by a point, whose coordinates may be signed as long as they are within the grid.
+impl<T, I: TryInto<usize>> Index<Point2<I>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2<I>) -> &T {
+        self.get_at(point).expect("Position out of bounds")
+    }
+}
+
+impl<T, I: TryInto<usize>> IndexMut<Point2<I>> for Grid<T> {
+    fn index_mut(&mut self, point: Point2<I>) -> &mut T {
+        self.get_at_mut(point).expect("Position out of bounds")
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for value in row {
+                write!(f, "{value}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the non-empty lines of the input, along with their index.
+fn parse_rows<T>(
+    input: &str,
+    mut f: impl FnMut(char) -> Result<T, ParseError>,
+) -> Result<Vec<(usize, Vec<T>)>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(column, c)| f(c).map_err(|e| e.at_column(column + 1).at_line(index + 1)))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok((index, row))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, |c| {
+            c.to_digit(10)
+                .ok_or_else(|| ParseError::new("Expected a digit"))
+        })
+    }
+
+    #[test]
+    fn parse_and_access() {
+        let grid = digits("123\n456\n").unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get(0, 1), Some(&4));
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.to_string(), "123\n456\n");
+    }
+
+    #[test]
+    fn access_by_point() {
+        let mut grid = digits("123\n456\n").unwrap();
+
+        assert_eq!(grid[Point2::new(2usize, 1)], 6);
+        assert_eq!(grid[Point2::new(0i32, 1)], 4);
+        assert_eq!(grid.get_at(Point2::new(-1isize, 0)), None);
+        assert_eq!(grid.get_at(Point2::new(1, 2)), None);
+        assert_eq!(grid.position(|&i| i == 5), Some(Point2::new(1, 1)));
+
+        grid[Point2::new(1i64, 0)] = 7;
+        assert_eq!(grid.row(0), &[1, 7, 3]);
+
+        let mut neighbors = grid.neighbors4_at(Point2::new(0, 1)).collect::<Vec<_>>();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![Point2::new(0, 0), Point2::new(1, 1)]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = digits("123\n4x6").unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), Some(2)));
+
+        let error = digits("123\n45").unwrap_err();
+        assert_eq!(error.line, Some(2));
+    }
+
+    #[test]
+    fn parse_ragged() {
+        let grid = Grid::parse_ragged("  #\n#", ' ', Ok).unwrap();
+        assert_eq!(grid.to_string(), "  #\n#  \n");
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 3, 0);
+
+        let mut corner = grid.neighbors4(0, 0).collect::<Vec<_>>();
+        corner.sort();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+
+        assert_eq!(grid.neighbors4(1, 1).count(), 4);
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+        assert_eq!(grid.neighbors8(2, 2).count(), 3);
+    }
+}
@@ -0,0 +1,66 @@
+use std::fs;
+use std::io::{self, stdin, IsTerminal};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_INPUTS_DIR: &str = "inputs";
+
+pub struct InputOptions {
+    pub file: Option<PathBuf>,
+    pub dir: PathBuf,
+    pub allow_stdin: bool,
+}
+
+impl Default for InputOptions {
+    fn default() -> Self {
+        InputOptions {
+            file: None,
+            dir: PathBuf::from(DEFAULT_INPUTS_DIR),
+            allow_stdin: true,
+        }
+    }
+}
+
+/// Loads the input of a day, in order of preference from:
+/// - the explicit file given in the options,
+/// - the `dayNN.txt` file in the inputs directory,
+/// - stdin, if allowed.
+pub fn load_input(day: u32, options: &InputOptions) -> Result<String, String> {
+    if let Some(file) = &options.file {
+        return read_file(file);
+    }
+
+    let path = options.dir.join(input_file_name(day));
+    if path.is_file() {
+        return read_file(&path);
+    }
+
+    if options.allow_stdin {
+        return read_stdin();
+    }
+
+    Err(format!("Input file not found: {}", path.display()))
+}
+
+/// Maps a day to its input file name, so that day `1` uses `day01.txt`.
+pub fn input_file_name(day: u32) -> String {
+    format!("day{day:02}.txt")
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Cannot read input file {}: {e}", path.display()))
+}
+
+fn read_stdin() -> Result<String, String> {
+    let stdin = stdin();
+
+    // Only prompt when someone is typing, and keep stdout clean for the results.
+    if stdin.is_terminal() {
+        eprintln!("Enter input:");
+    }
+
+    stdin
+        .lines()
+        .map(|i| i.map(|line| line + "\n"))
+        .collect::<io::Result<String>>()
+        .map_err(|e| format!("Cannot read input from stdin: {e}"))
+}
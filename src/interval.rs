@@ -0,0 +1,250 @@
+use crate::geometry::Number;
+use std::ops::RangeInclusive;
+
+/// A set of integers, stored as sorted and disjoint inclusive intervals.
+/// Adjacent intervals are merged, so each interval is separated from the next one by a gap.
+#[derive(Clone, Eq, PartialEq, Default, Debug)]
+pub struct IntervalSet<T> {
+    intervals: Vec<(T, T)>,
+}
+
+impl<T: Number> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { intervals: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns the number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |sum, &(start, end)| sum + (end - start) + T::ONE)
+    }
+
+    /// Returns the lowest value of the set.
+    pub fn first(&self) -> Option<T> {
+        self.intervals.first().map(|&(start, _)| start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|&(_, end)| end < value);
+        self.intervals
+            .get(index)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// Iterates over the intervals, in ascending order.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// Iterates over the missing values between the first and the last interval.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals
+            .windows(2)
+            .map(|pair| (pair[0].1 + T::ONE)..=(pair[1].0 - T::ONE))
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+
+        if start > end {
+            return;
+        }
+
+        let first = self
+            .intervals
+            .partition_point(|&(_, other_end)| !touches(other_end, start));
+
+        let mut last = first;
+
+        while let Some(&(other_start, other_end)) = self.intervals.get(last) {
+            if !touches(end, other_start) {
+                break;
+            }
+
+            start = start.min(other_start);
+            end = end.max(other_end);
+            last += 1;
+        }
+
+        self.intervals.splice(first..last, [(start, end)]);
+    }
+
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+
+        if start > end {
+            return;
+        }
+
+        let first = self
+            .intervals
+            .partition_point(|&(_, other_end)| other_end < start);
+        let last = self
+            .intervals
+            .partition_point(|&(other_start, _)| other_start <= end);
+
+        if first >= last {
+            return;
+        }
+
+        let mut remaining = vec![];
+
+        let (first_start, _) = self.intervals[first];
+        if first_start < start {
+            remaining.push((first_start, start - T::ONE));
+        }
+
+        let (_, last_end) = self.intervals[last - 1];
+        if last_end > end {
+            remaining.push((end + T::ONE, last_end));
+        }
+
+        self.intervals.splice(first..last, remaining);
+    }
+
+    /// Returns the values which are in either set.
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+        result.extend(other.ranges());
+        result
+    }
+
+    /// Returns the values which are in both sets.
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = IntervalSet::new();
+        let (mut a, mut b) = (self.intervals.iter(), other.intervals.iter());
+        let (mut current_a, mut current_b) = (a.next(), b.next());
+
+        while let (Some(&(start_a, end_a)), Some(&(start_b, end_b))) = (current_a, current_b) {
+            let (start, end) = (start_a.max(start_b), end_a.min(end_b));
+
+            if start <= end {
+                result.intervals.push((start, end));
+            }
+
+            if end_a < end_b {
+                current_a = a.next();
+            } else {
+                current_b = b.next();
+            }
+        }
+
+        result
+    }
+
+    /// Returns the values within `bounds` which are not in the set.
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> IntervalSet<T> {
+        let mut result = IntervalSet::from(bounds);
+
+        for range in self.ranges() {
+            result.remove(range);
+        }
+
+        result
+    }
+}
+
+/// Returns whether an interval ending at `end` overlaps or is adjacent to one starting at `start`.
+fn touches<T: Number>(end: T, start: T) -> bool {
+    // When `start` is after `end`, `end` is below the maximum value and can be stepped over
+    start <= end || end + T::ONE == start
+}
+
+impl<T: Number> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut result = IntervalSet::new();
+        result.insert(range);
+        result
+    }
+}
+
+impl<T: Number> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Number> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut result = IntervalSet::new();
+        result.extend(iter);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<RangeInclusive<i32>> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn insert_merges_intervals() {
+        let mut set = IntervalSet::from_iter([10..=12, 1..=3, 6..=7]);
+        assert_eq!(ranges(&set), vec![1..=3, 6..=7, 10..=12]);
+
+        set.insert(4..=4);
+        set.insert(8..=9);
+        assert_eq!(ranges(&set), vec![1..=4, 6..=12]);
+
+        set.insert(0..=20);
+        assert_eq!(ranges(&set), vec![0..=20]);
+
+        set.insert(RangeInclusive::new(5, 1));
+        assert_eq!(set.len(), 21);
+    }
+
+    #[test]
+    fn remove_splits_intervals() {
+        let mut set = IntervalSet::from_iter([0..=10, 20..=30]);
+
+        set.remove(5..=5);
+        assert_eq!(ranges(&set), vec![0..=4, 6..=10, 20..=30]);
+
+        set.remove(8..=25);
+        assert_eq!(ranges(&set), vec![0..=4, 6..=7, 26..=30]);
+
+        assert_eq!(set.first(), Some(0));
+        assert!(set.contains(7));
+        assert!(!set.contains(8));
+        assert_eq!(set.len(), 12);
+    }
+
+    #[test]
+    fn extreme_bounds() {
+        let mut set = IntervalSet::from_iter([i32::MAX..=i32::MAX, i32::MIN..=i32::MIN]);
+        assert_eq!(ranges(&set), vec![i32::MIN..=i32::MIN, i32::MAX..=i32::MAX]);
+
+        set.insert(i32::MIN..=-1);
+        set.insert(0..=i32::MAX - 1);
+        assert_eq!(ranges(&set), vec![i32::MIN..=i32::MAX]);
+
+        set.remove(i32::MIN..=i32::MIN);
+        set.remove(i32::MAX..=i32::MAX);
+        assert_eq!(ranges(&set), vec![i32::MIN + 1..=i32::MAX - 1]);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = IntervalSet::from_iter([0..=5, 10..=15]);
+        let b = IntervalSet::from_iter([3..=12]);
+
+        assert_eq!(ranges(&a.union(&b)), vec![0..=15]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![3..=5, 10..=12]);
+        assert_eq!(
+            ranges(&a.complement(-2..=20)),
+            vec![-2..=-1, 6..=9, 16..=20]
+        );
+        assert_eq!(a.gaps().collect::<Vec<_>>(), vec![6..=9]);
+        assert!(a.intersection(&IntervalSet::from(6..=9)).is_empty());
+    }
+}
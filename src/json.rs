@@ -0,0 +1,67 @@
+use std::fmt::{Display, Formatter, Write};
+
+/// A flat JSON object, written on a single line.
+#[derive(Default)]
+pub struct JsonObject {
+    fields: Vec<(String, String)>,
+}
+
+impl JsonObject {
+    pub fn new() -> JsonObject {
+        JsonObject::default()
+    }
+
+    pub fn string(self, key: &str, value: &str) -> JsonObject {
+        self.raw(key, quote(value))
+    }
+
+    pub fn number(self, key: &str, value: impl Display) -> JsonObject {
+        self.raw(key, value.to_string())
+    }
+
+    pub fn null(self, key: &str) -> JsonObject {
+        self.raw(key, "null".to_string())
+    }
+
+    fn raw(mut self, key: &str, value: String) -> JsonObject {
+        self.fields.push((quote(key), value));
+        self
+    }
+}
+
+impl Display for JsonObject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_char('{')?;
+
+        for (index, (key, value)) in self.fields.iter().enumerate() {
+            if index > 0 {
+                f.write_char(',')?;
+            }
+
+            write!(f, "{key}:{value}")?;
+        }
+
+        f.write_char('}')
+    }
+}
+
+/// Returns `value` as a JSON string literal.
+pub fn quote(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+    result
+}
@@ -0,0 +1,97 @@
+use crate::solution::Runnable;
+
+pub mod animate;
+pub mod answers;
+pub mod bench;
+pub mod common;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+mod day11b;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod diff;
+pub mod error;
+pub mod generate;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod json;
+pub mod log;
+pub mod parse;
+pub mod random;
+pub mod render;
+pub mod runner;
+pub mod solution;
+
+pub const DAYS: [u32; 25] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+];
+
+/// Returns the solution of the given day, with its default parameters.
+pub fn get_solution(day: u32) -> Option<Box<dyn Runnable>> {
+    Some(match day {
+        1 => Box::new(day1::Day1),
+        2 => Box::new(day2::Day2),
+        3 => Box::new(day3::Day3),
+        4 => Box::new(day4::Day4),
+        5 => Box::new(day5::Day5),
+        6 => Box::new(day6::Day6),
+        7 => Box::new(day7::Day7::default()),
+        8 => Box::new(day8::Day8),
+        9 => Box::new(day9::Day9),
+        10 => Box::new(day10::Day10::default()),
+        11 => Box::new(day11::Day11::default()),
+        12 => Box::new(day12::Day12),
+        13 => Box::new(day13::Day13),
+        14 => Box::new(day14::Day14),
+        15 => Box::new(day15::Day15::default()),
+        16 => Box::new(day16::Day16::default()),
+        17 => Box::new(day17::Day17::default()),
+        18 => Box::new(day18::Day18),
+        19 => Box::new(day19::Day19::default()),
+        20 => Box::new(day20::Day20::default()),
+        21 => Box::new(day21::Day21),
+        22 => Box::new(day22::Day22),
+        23 => Box::new(day23::Day23::default()),
+        24 => Box::new(day24::Day24),
+        25 => Box::new(day25::Day25),
+        _ => return None,
+    })
+}
+
+/// Returns the solution of the given day, with the given `(name, value)` pairs overriding its parameters.
+pub fn configure_solution(
+    day: u32,
+    params: &[(String, String)],
+) -> Result<Box<dyn Runnable>, String> {
+    let mut solution = get_solution(day).ok_or_else(|| format!("No solution for day {day}"))?;
+
+    for (name, value) in params {
+        solution.set_param(name, value)?;
+    }
+
+    Ok(solution)
+}
@@ -0,0 +1,40 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much the program prints besides the answers.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub enum Level {
+    /// Only the answers and errors.
+    Quiet,
+    /// The answers along with timings.
+    Normal,
+    /// Everything, including the diagnostics of the solutions.
+    Trace,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Normal as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => Level::Quiet,
+        1 => Level::Normal,
+        _ => Level::Trace,
+    }
+}
+
+pub fn enabled(level: Level) -> bool {
+    self::level() >= level
+}
+
+/// Prints a diagnostic to stderr when tracing is enabled. The arguments are not evaluated otherwise.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            eprintln!($($arg)*);
+        }
+    };
+}
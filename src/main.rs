@@ -1,30 +1,835 @@
-extern crate core;
-
-mod common;
-mod day1;
-mod day10;
-mod day11;
-mod day11b;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+use aoc_2022::animate::{Animation, DEFAULT_FPS};
+use aoc_2022::answers::{Answers, Verdict, DEFAULT_ANSWERS_FILE};
+use aoc_2022::bench::{measure, Stats};
+use aoc_2022::common::{Part, Parts};
+use aoc_2022::diff::{diff, DEFAULT_DIFF_SIZE};
+use aoc_2022::error::ParseError;
+use aoc_2022::generate::{generate, DEFAULT_SIZE};
+use aoc_2022::input::{load_input, InputOptions};
+use aoc_2022::json::JsonObject;
+use aoc_2022::log::{self, Level};
+use aoc_2022::render::{write_frames, Canvas, Frames, ImageFormat, DEFAULT_SCALE};
+use aoc_2022::runner::{run_days, DayError};
+use aoc_2022::solution::Runnable;
+use aoc_2022::{configure_solution, DAYS};
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "Usage: aoc-2022 [bench|generate|diff|render] <day|all> [--part 1|2] [--input <file>] [--inputs-dir <dir>] [--iterations <n>] [--seed <n>] [--seeds <n>] [--size <n>] [--output <dir>] [--image ppm|svg] [--every <n>] [--scale <n>] [--animate] [--fps <n>] [--answers <file>] [--format text|json] [--param <name>=<value>]... [--quiet|--verbose]";
+
+const DEFAULT_ITERATIONS: usize = 10;
+const DEFAULT_SEEDS: u64 = 100;
+const DEFAULT_OUTPUT_DIR: &str = "frames";
+
+struct Args {
+    mode: Mode,
+    days: Vec<u32>,
+    parts: Parts,
+    input: InputOptions,
+    answers: Option<PathBuf>,
+    output: PathBuf,
+    format: Format,
+    params: Vec<(String, String)>,
+    level: Level,
+}
+
+#[derive(Copy, Clone)]
+enum Mode {
+    Run,
+    Bench(usize),
+    Generate { seed: u64, size: usize },
+    Diff { seed: u64, seeds: u64, size: usize },
+    Render(RenderOptions),
+    Animate { fps: u32, every: usize },
+}
+
+#[derive(Copy, Clone)]
+struct RenderOptions {
+    image: ImageFormat,
+    /// Only one frame out of `every` is saved, or just the final picture when 0.
+    every: usize,
+    scale: usize,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
 
 fn main() {
-    day22::run();
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            eprintln!("{USAGE}");
+            exit(2);
+        }
+    };
+
+    log::set_level(args.level);
+
+    let answers = match load_answers(&args) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            exit(1);
+        }
+    };
+
+    let passed = match args.mode {
+        Mode::Generate { seed, size } => generate_input(args.days[0], seed, size),
+        Mode::Diff { seed, seeds, size } => run_diff(&args, seed..seed + seeds, size),
+        Mode::Run if args.format == Format::Json => run_json(&args, answers.as_ref()),
+        Mode::Run if args.days.len() > 1 => run_all(&args, answers.as_ref()),
+        _ => run_each(&args, answers.as_ref()),
+    };
+
+    if !passed {
+        exit(1);
+    }
+}
+
+/// Runs the days one after the other, and returns `false` on any failure.
+fn run_each(args: &Args, answers: Option<&Answers>) -> bool {
+    let show_header = args.days.len() > 1;
+    let mut failed = false;
+
+    for &day in &args.days {
+        if show_header {
+            println!("Day {day}");
+        }
+
+        let solution = configure_solution(day, &args.params).unwrap();
+
+        let input = match load_input(day, &args.input) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("Error: {error}");
+                failed = true;
+                continue;
+            }
+        };
+
+        let result = match args.mode {
+            Mode::Run => run(day, solution.as_ref(), &input, args.parts, answers),
+            Mode::Bench(iterations) => bench(solution.as_ref(), &input, args.parts, iterations),
+            Mode::Render(options) => render(day, solution.as_ref(), &input, args, options),
+            Mode::Animate { fps, every } => {
+                animate(day, solution.as_ref(), &input, args.parts, fps, every)
+            }
+            Mode::Generate { .. } | Mode::Diff { .. } => unreachable!(),
+        };
+
+        match result {
+            Ok(true) => {}
+            Ok(false) => failed = true,
+            Err(error) => {
+                print_parse_error(day, &input, &error);
+                failed = true;
+            }
+        }
+    }
+
+    !failed
+}
+
+/// Runs the days in parallel and prints a summary table, ordered by day.
+fn run_all(args: &Args, answers: Option<&Answers>) -> bool {
+    let (runs, duration) = measure(|| run_days(&args.days, args.parts, &args.input, &args.params));
+
+    let mut rows = vec![];
+    let mut errors = vec![];
+    let mut failed = false;
+
+    for run in &runs {
+        let result = match &run.result {
+            Ok(result) => result,
+            Err(error) => {
+                errors.push((run.day, error));
+                continue;
+            }
+        };
+
+        for part in &result.parts {
+            let (answer, check, passed) = match &part.answer {
+                Ok(answer) => {
+                    let (check, passed) = check_answer(answers, run.day, part.part, answer);
+                    (answer.trim_end().to_string(), check, passed)
+                }
+                Err(error) => (format!("Error: {error}"), "FAIL".to_string(), false),
+            };
+
+            failed |= !passed;
+
+            rows.push([
+                run.day.to_string(),
+                part.part.number().to_string(),
+                answer,
+                format!("{:.2?}", part.duration),
+                check,
+            ]);
+        }
+    }
+
+    let answer_width = rows
+        .iter()
+        .flat_map(|row| row[2].lines())
+        .map(|i| i.chars().count())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap();
+
+    let print_row = |[day, part, answer, time, check]: [&str; 5]| {
+        let row = format!("{day:>3}  {part:>4}  {answer:<answer_width$}  {time:>10}  {check}");
+        println!("{}", row.trim_end());
+    };
+
+    print_row(["Day", "Part", "Answer", "Time", ""]);
+
+    for [day, part, answer, time, check] in &rows {
+        let mut lines = answer.lines();
+        print_row([day, part, lines.next().unwrap_or(""), time, check]);
+
+        for line in lines {
+            print_row(["", "", line, "", ""]);
+        }
+    }
+
+    if log::enabled(Level::Normal) {
+        println!("Total: {duration:.2?}");
+    }
+
+    for (day, error) in errors {
+        match error {
+            DayError::Param(error) | DayError::Input(error) => {
+                eprintln!("Error: day {day}: {error}")
+            }
+            DayError::Parse { input, error } => print_parse_error(day, input, error),
+        }
+
+        failed = true;
+    }
+
+    !failed
+}
+
+/// Prints one JSON object per line for each day and part, and returns `false` on any failure.
+fn run_json(args: &Args, answers: Option<&Answers>) -> bool {
+    let mut failed = false;
+
+    for run in run_days(&args.days, args.parts, &args.input, &args.params) {
+        let result = match run.result {
+            Ok(result) => result,
+            Err(error) => {
+                let object = JsonObject::new().number("day", run.day);
+                let object = match error {
+                    DayError::Param(error) | DayError::Input(error) => {
+                        object.string("error", &error)
+                    }
+                    DayError::Parse { error, .. } => {
+                        let object = object.string("error", &error.message);
+                        let object = match error.line {
+                            Some(line) => object.number("line", line),
+                            None => object,
+                        };
+                        match error.column {
+                            Some(column) => object.number("column", column),
+                            None => object,
+                        }
+                    }
+                };
+
+                println!("{object}");
+                failed = true;
+                continue;
+            }
+        };
+
+        for part in &result.parts {
+            let object = JsonObject::new()
+                .number("day", run.day)
+                .number("part", part.part.number());
+
+            let answer = match &part.answer {
+                Ok(answer) => answer.trim_end(),
+                Err(error) => {
+                    let object = object
+                        .null("answer")
+                        .string("error", error)
+                        .number("parse_ns", result.parse_duration.as_nanos())
+                        .number("time_ns", part.duration.as_nanos());
+
+                    let object = match answers.map(|i| i.get(run.day, part.part)) {
+                        Some(Some(expected)) => {
+                            object.string("status", "fail").string("expected", expected)
+                        }
+                        Some(None) => object.string("status", "fail"),
+                        None => object,
+                    };
+
+                    println!("{object}");
+                    failed = true;
+                    continue;
+                }
+            };
+
+            // Multi-line answers are pictures which still need to be read by a human.
+            let object = match answer.contains('\n') {
+                true => object.null("answer").string("rendered", answer),
+                false => object.string("answer", answer),
+            };
+
+            let object = object
+                .number("parse_ns", result.parse_duration.as_nanos())
+                .number("time_ns", part.duration.as_nanos());
+
+            let object = match answers.map(|i| i.check(run.day, part.part, answer)) {
+                Some(Verdict::Pass) => object.string("status", "pass"),
+                Some(Verdict::Fail { expected }) => {
+                    failed = true;
+                    object
+                        .string("status", "fail")
+                        .string("expected", &expected)
+                }
+                Some(Verdict::Unknown) => object.string("status", "unknown"),
+                None => object,
+            };
+
+            println!("{object}");
+        }
+    }
+
+    !failed
+}
+
+/// Checks an answer against the known one, and returns the label to display along with whether it passed.
+fn check_answer(answers: Option<&Answers>, day: u32, part: Part, answer: &str) -> (String, bool) {
+    match answers.map(|i| i.check(day, part, answer)) {
+        Some(Verdict::Pass) => ("PASS".to_string(), true),
+        Some(Verdict::Fail { expected }) if expected.contains('\n') => {
+            (format!("FAIL, expected:\n{expected}\n"), false)
+        }
+        Some(Verdict::Fail { expected }) => (format!("FAIL, expected {expected}"), false),
+        Some(Verdict::Unknown) => ("UNKNOWN".to_string(), true),
+        None => (String::new(), true),
+    }
+}
+
+/// Loads the answers file given on the command line, or the one in the inputs directory if present.
+fn load_answers(args: &Args) -> Result<Option<Answers>, String> {
+    if let Some(path) = &args.answers {
+        return Answers::load(path).map(Some);
+    }
+
+    let path = args.input.dir.join(DEFAULT_ANSWERS_FILE);
+    match path.is_file() {
+        true => Answers::load(&path).map(Some),
+        false => Ok(None),
+    }
+}
+
+/// Prints the results, and returns `false` if any of them does not match the known answer.
+fn run(
+    day: u32,
+    solution: &dyn Runnable,
+    input: &str,
+    parts: Parts,
+    answers: Option<&Answers>,
+) -> Result<bool, ParseError> {
+    let result = solution.run(input, parts)?;
+    let mut times = vec![format!("parse {:.2?}", result.parse_duration)];
+    let mut passed = true;
+
+    for part in &result.parts {
+        let number = part.part.number();
+        times.push(format!("part {number} {:.2?}", part.duration));
+
+        let answer = match &part.answer {
+            Ok(answer) => answer,
+            Err(error) => {
+                eprintln!("Error: day {day} part {number}: {error}");
+                passed = false;
+                continue;
+            }
+        };
+
+        let verdict = match check_answer(answers, day, part.part, answer) {
+            (check, _) if check.is_empty() => String::new(),
+            (check, ok) => {
+                passed &= ok;
+                format!(" [{check}]")
+            }
+        };
+
+        if answer.contains('\n') {
+            println!("Result (part {number}):{verdict}\n{answer}");
+        } else {
+            println!("Result (part {number}): {answer}{verdict}");
+        }
+    }
+
+    if log::enabled(Level::Normal) {
+        println!("Time: {}", times.join(", "));
+    }
+
+    Ok(passed)
+}
+
+fn bench(
+    solution: &dyn Runnable,
+    input: &str,
+    parts: Parts,
+    iterations: usize,
+) -> Result<bool, ParseError> {
+    let result = solution.bench(input, parts, iterations)?;
+
+    let print_row = |name: &str, stats: &Stats| {
+        println!(
+            "{name:<8} {:>12} {:>12} {:>12}",
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.max),
+        );
+    };
+
+    println!("{:<8} {:>12} {:>12} {:>12}", "", "min", "median", "max");
+    print_row("parse", &result.parse);
+
+    for (part, stats) in &result.parts {
+        print_row(&format!("part {}", part.number()), stats);
+    }
+
+    Ok(true)
+}
+
+/// Saves the pictures of each part to the output directory.
+fn render(
+    day: u32,
+    solution: &dyn Runnable,
+    input: &str,
+    args: &Args,
+    options: RenderOptions,
+) -> Result<bool, ParseError> {
+    let dir = args.output.as_path();
+
+    if let Err(error) = fs::create_dir_all(dir) {
+        eprintln!("Error: cannot create {}: {error}", dir.display());
+        return Ok(false);
+    }
+
+    let mut rendered = false;
+
+    for part in [Part::One, Part::Two] {
+        if !args.parts.includes(part) {
+            continue;
+        }
+
+        let prefix = format!("day{day:02}-part{}", part.number());
+        let output = write_frames(dir, &prefix, options.image, options.scale);
+        let mut frames = Frames::new(options.every, output);
+
+        if !solution.render(input, part, &mut frames)? {
+            continue;
+        }
+
+        rendered = true;
+
+        match frames.into_result() {
+            Ok(count) => println!(
+                "Part {}: {count} {} written to {}",
+                part.number(),
+                if count == 1 { "frame" } else { "frames" },
+                frame_pattern(dir, &prefix, options.image).display()
+            ),
+            Err(error) => {
+                eprintln!(
+                    "Error: cannot write the frames to {}: {error}",
+                    dir.display()
+                );
+                return Ok(false);
+            }
+        }
+    }
+
+    if !rendered {
+        eprintln!("Error: day {day} has nothing to render");
+    }
+
+    Ok(rendered)
+}
+
+/// Draws the pictures of each part in the terminal, until the user quits.
+fn animate(
+    day: u32,
+    solution: &dyn Runnable,
+    input: &str,
+    parts: Parts,
+    fps: u32,
+    every: usize,
+) -> Result<bool, ParseError> {
+    let mut animation = Animation::new(fps);
+    let mut rendered = false;
+
+    for part in [Part::One, Part::Two] {
+        if !parts.includes(part) {
+            continue;
+        }
+
+        let title = format!("Day {day} part {}", part.number());
+        let mut frames = Frames::new(every, |index, canvas: &Canvas| {
+            animation.show(&title, index, canvas)
+        });
+
+        if !solution.render(input, part, &mut frames)? {
+            continue;
+        }
+
+        rendered = true;
+
+        match frames.into_result() {
+            Ok(_) => {}
+            Err(error) if error.kind() == ErrorKind::Interrupted => break,
+            Err(error) => {
+                drop(animation);
+                eprintln!("Error: cannot draw the frames: {error}");
+                return Ok(false);
+            }
+        }
+    }
+
+    drop(animation);
+
+    if !rendered {
+        eprintln!("Error: day {day} has nothing to render");
+    }
+
+    Ok(rendered)
+}
+
+fn frame_pattern(dir: &Path, prefix: &str, image: ImageFormat) -> PathBuf {
+    dir.join(format!("{prefix}-*.{}", image.extension()))
+}
+
+/// Prints a random input, along with what is needed to reproduce and solve it on stderr.
+fn generate_input(day: u32, seed: u64, size: usize) -> bool {
+    let generated = generate(day, seed, size).expect("Every day has a generator");
+    print!("{}", generated.input);
+
+    if log::enabled(Level::Normal) {
+        eprintln!("Seed: {seed}");
+
+        if !generated.params.is_empty() {
+            let params = generated
+                .params
+                .iter()
+                .map(|(name, value)| format!("--param {name}={value}"))
+                .collect::<Vec<_>>();
+
+            eprintln!("Solve with: {}", params.join(" "));
+        }
+    }
+
+    true
+}
+
+/// Compares each day with its reference solution on generated inputs, and returns `false` on any mismatch.
+fn run_diff(args: &Args, seeds: Range<u64>, size: usize) -> bool {
+    let mut failed = false;
+
+    for &day in &args.days {
+        let report = match diff(day, seeds.clone(), size, &args.params) {
+            Ok(report) => report,
+            Err(error) => {
+                eprintln!("Error: day {day}: {error}");
+                failed = true;
+                continue;
+            }
+        };
+
+        if report.compared == 0 {
+            if args.days.len() == 1 {
+                eprintln!("Error: day {day} has no reference solution");
+                failed = true;
+            }
+
+            continue;
+        }
+
+        for mismatch in &report.mismatches {
+            println!(
+                "Day {day}, seed {}, part {}: {}, reference {}",
+                mismatch.seed,
+                mismatch.part.number(),
+                mismatch.answer,
+                mismatch.reference
+            );
+        }
+
+        let status = match report.mismatches.len() {
+            0 => "all match".to_string(),
+            count => format!("{count} mismatches"),
+        };
+
+        println!("Day {day}: {} answers compared, {status}", report.compared);
+        failed |= !report.mismatches.is_empty();
+    }
+
+    !failed
+}
+
+/// Prints the error along with the offending input line, when it is known.
+fn print_parse_error(day: u32, input: &str, error: &ParseError) {
+    eprintln!("Error: day {day}: invalid input: {error}");
+
+    let Some(line) = error.line.and_then(|i| input.lines().nth(i - 1)) else {
+        return;
+    };
+
+    eprintln!("  | {line}");
+
+    if let Some(column) = error.column {
+        let padding = line
+            .chars()
+            .take(column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+
+        eprintln!("  | {padding}^");
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut command = None;
+    let mut seed = None;
+    let mut seeds = DEFAULT_SEEDS;
+    let mut size = None;
+    let mut iterations = DEFAULT_ITERATIONS;
+    let mut days = None;
+    let mut parts = Parts::Both;
+    let mut input = InputOptions::default();
+    let mut answers = None;
+    let mut output = PathBuf::from(DEFAULT_OUTPUT_DIR);
+    let mut image = ImageFormat::Ppm;
+    let mut every = 1;
+    let mut scale = DEFAULT_SCALE;
+    let mut animated = false;
+    let mut fps = DEFAULT_FPS;
+    let mut format = Format::Text;
+    let mut params = vec![];
+    let mut level = Level::Normal;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                parts = match args.next().as_deref() {
+                    Some("1") => Parts::Only(Part::One),
+                    Some("2") => Parts::Only(Part::Two),
+                    Some(other) => return Err(format!("Invalid part: {other}")),
+                    None => return Err("Missing value for --part".to_string()),
+                }
+            }
+            "--input" | "-i" => {
+                input.file = Some(PathBuf::from(
+                    args.next().ok_or("Missing value for --input")?,
+                ))
+            }
+            "--inputs-dir" => {
+                input.dir = PathBuf::from(args.next().ok_or("Missing value for --inputs-dir")?)
+            }
+            "--answers" => {
+                answers = Some(PathBuf::from(
+                    args.next().ok_or("Missing value for --answers")?,
+                ))
+            }
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    Some(other) => return Err(format!("Invalid format: {other}")),
+                    None => return Err("Missing value for --format".to_string()),
+                }
+            }
+            "--param" => {
+                let param = args.next().ok_or("Missing value for --param")?;
+                let (name, value) = param.split_once('=').ok_or_else(|| {
+                    format!("Invalid parameter: {param}, expected <name>=<value>")
+                })?;
+
+                params.push((name.to_string(), value.to_string()));
+            }
+            "--iterations" | "-n" => {
+                iterations = match args.next().map(|i| i.parse()) {
+                    Some(Ok(n)) if n > 0 => n,
+                    Some(_) => return Err("Invalid number of iterations".to_string()),
+                    None => return Err("Missing value for --iterations".to_string()),
+                }
+            }
+            "--seed" => {
+                seed = match args.next().map(|i| i.parse()) {
+                    Some(Ok(n)) => Some(n),
+                    Some(_) => return Err("Invalid seed".to_string()),
+                    None => return Err("Missing value for --seed".to_string()),
+                }
+            }
+            "--seeds" => {
+                seeds = match args.next().map(|i| i.parse()) {
+                    Some(Ok(n)) if n > 0 => n,
+                    Some(_) => return Err("Invalid number of seeds".to_string()),
+                    None => return Err("Missing value for --seeds".to_string()),
+                }
+            }
+            "--size" => {
+                size = match args.next().map(|i| i.parse()) {
+                    Some(Ok(n)) if n > 0 => Some(n),
+                    Some(_) => return Err("Invalid size".to_string()),
+                    None => return Err("Missing value for --size".to_string()),
+                }
+            }
+            "--output" | "-o" => {
+                output = PathBuf::from(args.next().ok_or("Missing value for --output")?)
+            }
+            "--image" => image = args.next().ok_or("Missing value for --image")?.parse()?,
+            "--every" => {
+                every = match args.next().map(|i| i.parse()) {
+                    Some(Ok(n)) => n,
+                    Some(_) => return Err("Invalid frame interval".to_string()),
+                    None => return Err("Missing value for --every".to_string()),
+                }
+            }
+            "--scale" => {
+                scale = match args.next().map(|i| i.parse()) {
+                    Some(Ok(n)) if n > 0 => n,
+                    Some(_) => return Err("Invalid scale".to_string()),
+                    None => return Err("Missing value for --scale".to_string()),
+                }
+            }
+            "--animate" => animated = true,
+            "--fps" => {
+                fps = match args.next().map(|i| i.parse()) {
+                    Some(Ok(n)) if n > 0 => n,
+                    Some(_) => return Err("Invalid frame rate".to_string()),
+                    None => return Err("Missing value for --fps".to_string()),
+                }
+            }
+            "--quiet" | "-q" => level = Level::Quiet,
+            "--verbose" | "-v" => level = Level::Trace,
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                exit(0);
+            }
+            _ if arg.starts_with('-') => return Err(format!("Unknown option: {arg}")),
+            _ if days.is_some() => return Err(format!("Unexpected argument: {arg}")),
+            "bench" | "generate" | "diff" | "render" if command.is_none() => {
+                command = Some(arg.clone())
+            }
+            "all" => days = Some(DAYS.to_vec()),
+            day => {
+                let day = day.strip_prefix("day").unwrap_or(day);
+                match day.parse::<u32>() {
+                    Ok(day) if DAYS.contains(&day) => days = Some(vec![day]),
+                    _ => return Err(format!("Unknown day: {arg}")),
+                }
+            }
+        }
+    }
+
+    let days = days.ok_or("Missing day")?;
+
+    if days.len() > 1 {
+        if input.file.is_some() {
+            return Err("--input requires a single day".to_string());
+        }
+
+        input.allow_stdin = false;
+    }
+
+    if !params.is_empty() {
+        if days.len() > 1 {
+            return Err("--param requires a single day".to_string());
+        }
+
+        configure_solution(days[0], &params)?;
+    }
+
+    if let (&[day], Parts::Only(part)) = (days.as_slice(), parts) {
+        if !configure_solution(day, &[])?.parts().includes(part) {
+            return Err(format!("Day {day} has no part {}", part.number()));
+        }
+    }
+
+    let command = command.as_deref();
+
+    // Only the answers of a plain run are given as JSON
+    if format == Format::Json {
+        if let Some(command) = command {
+            return Err(format!("--format json is not supported by {command}"));
+        }
+
+        if animated {
+            return Err("--format json is not supported by --animate".to_string());
+        }
+    }
+
+    if let Some(command @ ("generate" | "render")) = command {
+        if days.len() > 1 {
+            return Err(format!("{command} requires a single day"));
+        }
+    }
+
+    if animated {
+        if let Some(command) = command {
+            return Err(format!("--animate is not supported by {command}"));
+        }
+
+        if days.len() > 1 {
+            return Err("--animate requires a single day".to_string());
+        }
+    }
+
+    let mode = match command {
+        Some("bench") => Mode::Bench(iterations),
+        Some("generate") => Mode::Generate {
+            seed: seed.unwrap_or_else(random_seed),
+            size: size.unwrap_or(DEFAULT_SIZE),
+        },
+        Some("diff") => Mode::Diff {
+            seed: match seed.unwrap_or(0) {
+                seed if seed.checked_add(seeds).is_some() => seed,
+                _ => return Err("Invalid seed, the seeds go past the largest one".to_string()),
+            },
+            seeds,
+            size: size.unwrap_or(DEFAULT_DIFF_SIZE),
+        },
+        Some("render") => Mode::Render(RenderOptions {
+            image,
+            every,
+            scale,
+        }),
+        _ if animated => Mode::Animate { fps, every },
+        _ => Mode::Run,
+    };
+
+    Ok(Args {
+        mode,
+        days,
+        parts,
+        input,
+        answers,
+        output,
+        format,
+        params,
+        level,
+    })
+}
+
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |i| i.as_nanos() as u64)
 }
@@ -0,0 +1,152 @@
+use crate::error::{parse_token, ParseError};
+use std::str::FromStr;
+
+/// The fields extracted from a line by [`scan`], returned in order.
+#[derive(Debug)]
+pub struct Fields<'a> {
+    line: &'a str,
+    fields: Vec<&'a str>,
+    next: usize,
+}
+
+/// Matches `line` against `pattern`, where each `{}` stands for a non-empty field and the rest must appear as is.
+/// A field extends up to the next occurrence of the text which follows it in the pattern, or to the end of the line.
+pub fn scan<'a>(line: &'a str, pattern: &str) -> Result<Fields<'a>, ParseError> {
+    let mut literals = pattern.split("{}");
+    let mut fields = vec![];
+
+    let first = literals.next().unwrap();
+    let mut rest = line
+        .strip_prefix(first)
+        .ok_or_else(|| ParseError::at(line, line, expected(first)))?;
+
+    for literal in literals {
+        let end = if literal.is_empty() {
+            rest.len()
+        } else {
+            rest.find(literal)
+                .ok_or_else(|| ParseError::at(line, &rest[rest.len()..], expected(literal)))?
+        };
+
+        if end == 0 {
+            return Err(ParseError::at(line, rest, "Expected a value"));
+        }
+
+        fields.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+
+    if !rest.is_empty() {
+        return Err(ParseError::at(line, rest, "Unexpected text"));
+    }
+
+    Ok(Fields {
+        line,
+        fields,
+        next: 0,
+    })
+}
+
+/// Scans a line along with its 0-based index, as returned by `lines().enumerate()`,
+/// and reports the line number in the errors of `f` as well.
+pub fn scan_line<'a, T>(
+    (index, line): (usize, &'a str),
+    pattern: &str,
+    f: impl FnOnce(Fields<'a>) -> Result<T, ParseError>,
+) -> Result<T, ParseError> {
+    scan(line, pattern)
+        .and_then(f)
+        .map_err(|e| e.at_line(index + 1))
+}
+
+fn expected(literal: &str) -> String {
+    format!("Expected '{}'", literal.trim())
+}
+
+impl<'a> Fields<'a> {
+    /// Parses the next field.
+    pub fn parse<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        parse_token(self.line, self.text())
+    }
+
+    /// Parses the next field as a list of values separated by `separator`.
+    pub fn list<T: FromStr>(&mut self, separator: &str) -> Result<Vec<T>, ParseError> {
+        let line = self.line;
+
+        self.text()
+            .split(separator)
+            .map(|item| parse_token(line, item.trim()))
+            .collect()
+    }
+
+    /// Returns the next field as is.
+    pub fn text(&mut self) -> &'a str {
+        let field = self
+            .fields
+            .get(self.next)
+            .expect("Not enough fields in the pattern");
+        self.next += 1;
+        field
+    }
+
+    pub fn skip(&mut self, count: usize) {
+        for _ in 0..count {
+            self.text();
+        }
+    }
+
+    /// Creates an error about the last field which was read.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        let field = self
+            .next
+            .checked_sub(1)
+            .map_or(self.line, |i| self.fields[i]);
+        ParseError::at(self.line, field, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_fields() {
+        let line = "Valve AA has flow rate=0; tunnels lead to valves DD, II";
+        let mut fields = scan(line, "Valve {} has flow rate={}; {} to {}").unwrap();
+
+        assert_eq!(fields.text(), "AA");
+        assert_eq!(fields.parse::<u32>(), Ok(0));
+        assert_eq!(fields.text(), "tunnels lead");
+        assert_eq!(fields.text(), "valves DD, II");
+
+        let mut fields = scan("items: 1, 2,3", "items: {}").unwrap();
+        assert_eq!(fields.list::<u32>(","), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn reports_positions() {
+        let error = scan("move 1 to 3", "move {} from {} to {}").unwrap_err();
+        assert_eq!(error.message, "Expected 'from'");
+        assert_eq!(error.column, Some(12));
+
+        let error = scan("mv 1", "move {}").unwrap_err();
+        assert_eq!(error.column, Some(1));
+
+        let error = scan("x=1, y=", "x={}, y={}").unwrap_err();
+        assert_eq!(
+            (error.message.as_str(), error.column),
+            ("Expected a value", Some(8))
+        );
+
+        let error = scan("x=1, y=2 and more", "x={}, y={} ").unwrap_err();
+        assert_eq!(error.column, Some(10));
+
+        let mut fields = scan("x=1, y=z", "x={}, y={}").unwrap();
+        assert_eq!(fields.parse::<i32>(), Ok(1));
+        assert_eq!(fields.parse::<i32>().unwrap_err().column, Some(8));
+        assert_eq!(fields.error("Bad").column, Some(8));
+
+        let error = scan_line((2, "y"), "x", Ok).err().unwrap();
+        assert_eq!(error.line, Some(3));
+    }
+}
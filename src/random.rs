@@ -0,0 +1,96 @@
+use std::ops::RangeInclusive;
+
+/// A small pseudo-random number generator (SplitMix64), so that generated inputs can be reproduced from their seed.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in the given range, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "Empty range");
+
+        let span = end.abs_diff(start).wrapping_add(1);
+
+        match span {
+            0 => self.next_u64() as i64, // The whole i64 range
+            span => start.wrapping_add((self.next_u64() % span) as i64),
+        }
+    }
+
+    /// Returns an index below `len`, which must not be zero.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "Empty range");
+        (self.next_u64() % len as u64) as usize
+    }
+
+    /// Returns `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_reproducible() {
+        let values = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.range(-3..=3)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(values(42), values(42));
+        assert_ne!(values(42), values(43));
+        assert!(values(1).iter().all(|i| (-3..=3).contains(i)));
+    }
+
+    #[test]
+    fn covers_extreme_ranges() {
+        let mut rng = Rng::new(7);
+        let values = (0..20)
+            .map(|_| rng.range(i64::MIN..=i64::MAX))
+            .collect::<Vec<_>>();
+
+        assert!(values.iter().any(|&i| i < 0) && values.iter().any(|&i| i > 0));
+        assert_eq!(rng.range(i64::MAX..=i64::MAX), i64::MAX);
+        assert_eq!(rng.range(i64::MIN..=i64::MIN), i64::MIN);
+        assert!(rng.range(i64::MIN..=i64::MIN + 1) <= i64::MIN + 1);
+    }
+
+    #[test]
+    fn shuffle_keeps_items() {
+        let mut rng = Rng::new(1);
+        let mut items = (0..20).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,364 @@
+use crate::grid::Grid;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The default number of pixels per cell in the saved images.
+pub const DEFAULT_SCALE: usize = 4;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::new(0, 0, 0);
+    pub const WHITE: Color = Color::new(255, 255, 255);
+    pub const GRAY: Color = Color::new(128, 128, 128);
+    pub const DARK_GRAY: Color = Color::new(48, 48, 48);
+    pub const RED: Color = Color::new(220, 40, 40);
+    pub const GREEN: Color = Color::new(40, 180, 60);
+    pub const BLUE: Color = Color::new(50, 100, 230);
+    pub const YELLOW: Color = Color::new(240, 210, 40);
+    pub const SAND: Color = Color::new(230, 190, 120);
+    pub const BROWN: Color = Color::new(120, 80, 40);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+
+    /// Returns a bright color of the given hue, going from 0 to 1 through red, yellow, green, cyan, blue and magenta.
+    pub fn hue(h: f64) -> Color {
+        let h = h.rem_euclid(1.0) * 6.0;
+        let x = 1.0 - (h % 2.0 - 1.0).abs();
+
+        let (r, g, b) = match h as usize {
+            0 => (1.0, x, 0.0),
+            1 => (x, 1.0, 0.0),
+            2 => (0.0, 1.0, x),
+            3 => (0.0, x, 1.0),
+            4 => (x, 0.0, 1.0),
+            _ => (1.0, 0.0, x),
+        };
+
+        let channel = |v: f64| (55.0 + v * 200.0).round() as u8;
+        Color::new(channel(r), channel(g), channel(b))
+    }
+
+    /// Blends this color into `other`, where `t` goes from 0 (this color) to 1 (`other`).
+    pub fn mix(self, other: Color, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Color::new(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+}
+
+/// A picture made of square cells of a single color each, with `y` going down.
+#[derive(Clone)]
+pub struct Canvas {
+    cells: Grid<Color>,
+    background: Color,
+    focus: Option<(usize, usize)>,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize, background: Color) -> Canvas {
+        Canvas {
+            cells: Grid::new(width, height, background),
+            background,
+            focus: None,
+        }
+    }
+
+    /// Draws each cell of a grid with the color given by `f`.
+    pub fn from_grid<T>(grid: &Grid<T>, background: Color, f: impl Fn(&T) -> Color) -> Canvas {
+        Canvas {
+            cells: grid.map(f),
+            background,
+            focus: None,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Color {
+        self.cells[(x, y)]
+    }
+
+    /// Sets the color of a cell. Cells outside the canvas are ignored, so coordinates may be signed.
+    pub fn set<I: TryInto<usize>>(&mut self, x: I, y: I, color: Color) {
+        if let Some(cell) = self.cells.get_mut(x, y) {
+            *cell = color;
+        }
+    }
+
+    /// Returns the cell where the action happens, which is kept in view when only a part of the canvas
+    /// can be shown. Defaults to the center.
+    pub fn focus(&self) -> (usize, usize) {
+        self.focus.unwrap_or((self.width() / 2, self.height() / 2))
+    }
+
+    pub fn set_focus(&mut self, x: usize, y: usize) {
+        self.focus = Some((x, y));
+    }
+
+    /// Encodes the canvas as a binary PPM image, with `scale` pixels per cell.
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let scale = scale.max(1);
+        let (width, height) = (self.width() * scale, self.height() * scale);
+
+        let mut data = format!("P6\n{width} {height}\n255\n").into_bytes();
+        data.reserve(width * height * 3);
+
+        for row in self.cells.rows() {
+            let line = row
+                .iter()
+                .flat_map(|c| [c.r, c.g, c.b].repeat(scale))
+                .collect::<Vec<_>>();
+
+            for _ in 0..scale {
+                data.extend_from_slice(&line);
+            }
+        }
+
+        data
+    }
+
+    /// Encodes the canvas as an SVG image, with cells of `scale` pixels. Adjacent cells of the same color
+    /// on a row are merged into a single rectangle.
+    pub fn to_svg(&self, scale: usize) -> String {
+        let scale = scale.max(1);
+        let (width, height) = (self.width(), self.height());
+
+        let mut svg = String::new();
+
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {width} {height}" shape-rendering="crispEdges">"#,
+            width * scale,
+            height * scale,
+        )
+        .unwrap();
+
+        writeln!(
+            svg,
+            r#"<rect width="{width}" height="{height}" fill="{}"/>"#,
+            hex(self.background)
+        )
+        .unwrap();
+
+        for (y, row) in self.cells.rows().enumerate() {
+            let mut x = 0;
+
+            while x < row.len() {
+                let color = row[x];
+                let run = row[x..].iter().take_while(|&&c| c == color).count();
+
+                if color != self.background {
+                    writeln!(
+                        svg,
+                        r#"<rect x="{x}" y="{y}" width="{run}" height="1" fill="{}"/>"#,
+                        hex(color)
+                    )
+                    .unwrap();
+                }
+
+                x += run;
+            }
+        }
+
+        svg += "</svg>\n";
+        svg
+    }
+}
+
+fn hex(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ImageFormat {
+    Ppm,
+    Svg,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Svg => "svg",
+        }
+    }
+
+    pub fn encode(&self, canvas: &Canvas, scale: usize) -> Vec<u8> {
+        match self {
+            ImageFormat::Ppm => canvas.to_ppm(scale),
+            ImageFormat::Svg => canvas.to_svg(scale).into_bytes(),
+        }
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ppm" => Ok(ImageFormat::Ppm),
+            "svg" => Ok(ImageFormat::Svg),
+            _ => Err(format!("Invalid image format: {s}")),
+        }
+    }
+}
+
+type FrameOutput<'a> = Box<dyn FnMut(usize, &Canvas) -> io::Result<()> + 'a>;
+
+/// The frames of an animation, handed to an output as they are drawn.
+///
+/// A renderer pushes a frame for each step of its solution, then finishes with the final picture.
+/// Only one out of every `every` frames is drawn, and the final picture is always kept, so an `every` of 0
+/// gives a single image.
+pub struct Frames<'a> {
+    every: usize,
+    pushed: usize,
+    written: usize,
+    last_written: bool,
+    output: FrameOutput<'a>,
+    error: Option<io::Error>,
+}
+
+impl<'a> Frames<'a> {
+    /// Creates the frames, where `output` receives each kept frame along with its index, starting from 0.
+    pub fn new(
+        every: usize,
+        output: impl FnMut(usize, &Canvas) -> io::Result<()> + 'a,
+    ) -> Frames<'a> {
+        Frames {
+            every,
+            pushed: 0,
+            written: 0,
+            last_written: false,
+            output: Box::new(output),
+            error: None,
+        }
+    }
+
+    /// Adds an intermediate frame, which is only drawn by `draw` when it is kept.
+    pub fn push(&mut self, draw: impl FnOnce() -> Canvas) {
+        let keep = self.every != 0 && self.pushed.is_multiple_of(self.every);
+        self.pushed += 1;
+        self.last_written = keep;
+
+        if keep {
+            self.write(&draw());
+        }
+    }
+
+    /// Adds the final picture, unless it has just been kept as the last pushed frame.
+    pub fn finish(&mut self, draw: impl FnOnce() -> Canvas) {
+        if !self.last_written {
+            self.write(&draw());
+            self.last_written = true;
+        }
+    }
+
+    fn write(&mut self, canvas: &Canvas) {
+        if self.error.is_some() {
+            return;
+        }
+
+        match (self.output)(self.written, canvas) {
+            Ok(()) => self.written += 1,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    /// Returns the number of frames written, or the first error of the output.
+    pub fn into_result(self) -> io::Result<usize> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.written),
+        }
+    }
+}
+
+/// Returns a frame output which saves each frame to `<dir>/<prefix>-<number>.<extension>`, numbered from 1.
+pub fn write_frames<'a>(
+    dir: &'a Path,
+    prefix: &'a str,
+    format: ImageFormat,
+    scale: usize,
+) -> impl FnMut(usize, &Canvas) -> io::Result<()> + 'a {
+    move |index, canvas| {
+        let path = dir.join(format!("{prefix}-{:05}.{}", index + 1, format.extension()));
+        fs::write(path, format.encode(canvas, scale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canvas() -> Canvas {
+        let mut canvas = Canvas::new(3, 2, Color::BLACK);
+        canvas.set(0, 0, Color::WHITE);
+        canvas.set(1, 0, Color::WHITE);
+        canvas.set(2, 1, Color::RED);
+        canvas.set(-1, 5, Color::RED);
+        canvas
+    }
+
+    #[test]
+    fn ppm_image() {
+        let ppm = canvas().to_ppm(2);
+        let header = b"P6\n6 4\n255\n";
+
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        assert_eq!(&ppm[header.len()..header.len() + 6], &[255; 6]);
+    }
+
+    #[test]
+    fn svg_image() {
+        let svg = canvas().to_svg(10);
+
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="20""#)
+        );
+        assert!(svg.contains(r##"<rect x="0" y="0" width="2" height="1" fill="#ffffff"/>"##));
+        assert!(svg.contains(r##"<rect x="2" y="1" width="1" height="1" fill="#dc2828"/>"##));
+        assert_eq!(svg.matches("<rect").count(), 3);
+    }
+
+    #[test]
+    fn keeps_every_nth_frame() {
+        let kept = |every: usize, count: usize| {
+            let mut frames = Frames::new(every, |_, _: &Canvas| Ok(()));
+
+            for _ in 0..count {
+                frames.push(canvas);
+            }
+
+            frames.finish(canvas);
+            frames.into_result().unwrap()
+        };
+
+        assert_eq!(kept(1, 5), 5);
+        assert_eq!(kept(2, 5), 3);
+        assert_eq!(kept(2, 4), 3);
+        assert_eq!(kept(0, 5), 1);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::common::Parts;
+use crate::configure_solution;
+use crate::error::ParseError;
+use crate::input::{load_input, InputOptions};
+use crate::solution::RunResult;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+pub struct DayRun {
+    pub day: u32,
+    pub result: Result<RunResult, DayError>,
+}
+
+pub enum DayError {
+    Param(String),
+    Input(String),
+    Parse { input: String, error: ParseError },
+}
+
+/// Runs the given days on worker threads, and returns their results in the same order as `days`.
+/// The `params` override the puzzle parameters of every day.
+pub fn run_days(
+    days: &[u32],
+    parts: Parts,
+    options: &InputOptions,
+    params: &[(String, String)],
+) -> Vec<DayRun> {
+    let workers = thread::available_parallelism()
+        .map(|i| i.get())
+        .unwrap_or(1)
+        .min(days.len());
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..days.len()).map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(index) else {
+                    break;
+                };
+
+                let run = DayRun {
+                    day,
+                    result: run_day(day, parts, options, params),
+                };
+
+                results.lock().unwrap()[index] = Some(run);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|i| i.unwrap())
+        .collect()
+}
+
+fn run_day(
+    day: u32,
+    parts: Parts,
+    options: &InputOptions,
+    params: &[(String, String)],
+) -> Result<RunResult, DayError> {
+    let solution = configure_solution(day, params).map_err(DayError::Param)?;
+    let input = load_input(day, options).map_err(DayError::Input)?;
+
+    solution
+        .run(&input, parts)
+        .map_err(|error| DayError::Parse { input, error })
+}
@@ -0,0 +1,280 @@
+use crate::bench::{measure, Stats};
+use crate::common::{Part, Parts};
+use crate::error::ParseError;
+use crate::render::Frames;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::hint::black_box;
+use std::time::Duration;
+
+/// A puzzle solution: parses the input text once, then solves each part from it.
+pub trait Solution {
+    type Input;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    /// The parts which have been solved.
+    const PARTS: Parts = Parts::Both;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(&self, input: &Self::Input) -> Self::Answer1;
+
+    /// Solves part 2. The days without a second part leave it out of [`PARTS`](Solution::PARTS)
+    /// and do not implement it, using [`Infallible`] as their `Answer2`.
+    fn part2(&self, _input: &Self::Input) -> Self::Answer2 {
+        unreachable!("Only the parts in PARTS are solved")
+    }
+
+    /// The names and current values of the puzzle parameters. See [`params!`](crate::params).
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![]
+    }
+
+    /// Overrides a puzzle parameter, returning an error message when it is unknown or the value is invalid.
+    fn set_param(&mut self, name: &str, _value: &str) -> Result<(), String> {
+        Err(unknown_param(name, &self.params()))
+    }
+
+    /// Solves a part the slow but obviously correct way, to check the shortcuts taken by the solution.
+    /// Returns `None` when the part has no reference solution.
+    fn reference(&self, _input: &Self::Input, _part: Part) -> Option<String> {
+        None
+    }
+
+    /// Draws the pictures of a part into `frames`, step by step.
+    /// Returns `false` when the part has nothing to draw.
+    fn render(&self, _input: &Self::Input, _part: Part, _frames: &mut Frames) -> bool {
+        false
+    }
+}
+
+/// The answer to a part, or the reason why the input has none.
+pub trait Answer {
+    fn to_answer(&self) -> Result<String, String>;
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn to_answer(&self) -> Result<String, String> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+impl_answer!(i32, i64, isize, u32, u64, usize, String);
+
+/// The answer of the parts which do not exist.
+impl Answer for Infallible {
+    fn to_answer(&self) -> Result<String, String> {
+        match *self {}
+    }
+}
+
+/// Answers for the inputs which may have none, such as a maze without a path. The error is reported as a failure.
+impl<T: Answer, E: Display> Answer for Result<T, E> {
+    fn to_answer(&self) -> Result<String, String> {
+        match self {
+            Ok(answer) => answer.to_answer(),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+}
+
+/// Implements [`Solution::params`] and [`Solution::set_param`] in a `Solution` impl,
+/// for the given fields of the solution struct.
+#[macro_export]
+macro_rules! params {
+    ($($name:ident),* $(,)?) => {
+        fn params(&self) -> Vec<(&'static str, String)> {
+            vec![$((stringify!($name), self.$name.to_string())),*]
+        }
+
+        fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+            match name {
+                $(stringify!($name) => {
+                    self.$name = value
+                        .parse()
+                        .map_err(|_| format!("Invalid value for {name}: '{value}'"))?;
+                })*
+                _ => return Err($crate::solution::unknown_param(name, &self.params())),
+            }
+
+            Ok(())
+        }
+    };
+}
+
+pub fn unknown_param(name: &str, params: &[(&'static str, String)]) -> String {
+    if params.is_empty() {
+        return format!("Unknown parameter '{name}', this day has none");
+    }
+
+    let params = params
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>();
+
+    format!(
+        "Unknown parameter '{name}', expected one of: {}",
+        params.join(", ")
+    )
+}
+
+pub struct PartResult {
+    pub part: Part,
+    /// The answer, or the error which kept the part from having one.
+    pub answer: Result<String, String>,
+    pub duration: Duration,
+}
+
+pub struct RunResult {
+    pub parse_duration: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+/// The answers of the solution and of its reference solution for a part.
+pub struct Comparison {
+    pub part: Part,
+    pub answer: String,
+    pub reference: String,
+}
+
+impl Comparison {
+    pub fn matches(&self) -> bool {
+        self.answer == self.reference
+    }
+}
+
+pub struct BenchResult {
+    pub parse: Stats,
+    pub parts: Vec<(Part, Stats)>,
+}
+
+/// Object-safe view of a [`Solution`], used by the runner.
+pub trait Runnable {
+    /// The parts which have been solved, see [`Solution::PARTS`].
+    fn parts(&self) -> Parts;
+    fn params(&self) -> Vec<(&'static str, String)>;
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String>;
+
+    fn run(&self, input: &str, parts: Parts) -> Result<RunResult, ParseError>;
+
+    /// Runs the parse step and each part `iterations` times.
+    fn bench(
+        &self,
+        input: &str,
+        parts: Parts,
+        iterations: usize,
+    ) -> Result<BenchResult, ParseError>;
+
+    /// Solves the parts which have a reference solution with both the solution and the reference.
+    fn compare(&self, input: &str, parts: Parts) -> Result<Vec<Comparison>, ParseError>;
+
+    /// Parses the input and draws the pictures of a part, see [`Solution::render`].
+    fn render(&self, input: &str, part: Part, frames: &mut Frames) -> Result<bool, ParseError>;
+}
+
+impl<S: Solution> Runnable for S {
+    fn parts(&self) -> Parts {
+        S::PARTS
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        Solution::params(self)
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        Solution::set_param(self, name, value)
+    }
+
+    fn run(&self, input: &str, parts: Parts) -> Result<RunResult, ParseError> {
+        let (input, parse_duration) = measure(|| self.parse(input));
+        let input = input?;
+
+        let parts = selected_parts::<S>(parts)
+            .map(|part| {
+                let (answer, duration) = measure(|| solve(self, &input, part));
+                PartResult {
+                    part,
+                    answer,
+                    duration,
+                }
+            })
+            .collect();
+
+        Ok(RunResult {
+            parse_duration,
+            parts,
+        })
+    }
+
+    fn bench(
+        &self,
+        input: &str,
+        parts: Parts,
+        iterations: usize,
+    ) -> Result<BenchResult, ParseError> {
+        let mut parse_samples = vec![];
+        let mut parsed = None;
+
+        for _ in 0..iterations.max(1) {
+            let (result, duration) = measure(|| self.parse(input));
+            parse_samples.push(duration);
+            parsed = Some(result?);
+        }
+
+        let input = parsed.unwrap();
+
+        let parts = selected_parts::<S>(parts)
+            .map(|part| {
+                let samples = (0..iterations.max(1))
+                    .map(|_| measure(|| black_box(solve(self, &input, part))).1)
+                    .collect();
+
+                (part, Stats::new(samples))
+            })
+            .collect();
+
+        Ok(BenchResult {
+            parse: Stats::new(parse_samples),
+            parts,
+        })
+    }
+
+    fn compare(&self, input: &str, parts: Parts) -> Result<Vec<Comparison>, ParseError> {
+        let input = self.parse(input)?;
+
+        let comparisons = selected_parts::<S>(parts)
+            .filter_map(|part| {
+                let reference = self.reference(&input, part)?;
+                Some(Comparison {
+                    part,
+                    answer: solve(self, &input, part).unwrap_or_else(|e| format!("Error: {e}")),
+                    reference,
+                })
+            })
+            .collect();
+
+        Ok(comparisons)
+    }
+
+    fn render(&self, input: &str, part: Part, frames: &mut Frames) -> Result<bool, ParseError> {
+        let input = self.parse(input)?;
+        Ok(Solution::render(self, &input, part, frames))
+    }
+}
+
+fn selected_parts<S: Solution>(parts: Parts) -> impl Iterator<Item = Part> {
+    [Part::One, Part::Two]
+        .into_iter()
+        .filter(move |&part| parts.includes(part) && S::PARTS.includes(part))
+}
+
+fn solve<S: Solution>(solution: &S, input: &S::Input, part: Part) -> Result<String, String> {
+    match part {
+        Part::One => solution.part1(input).to_answer(),
+        Part::Two => solution.part2(input).to_answer(),
+    }
+}